@@ -6,6 +6,12 @@ use crate::{card::Card, suit::Suit};
 pub enum Action {
     /// None means play without trump
     PickTrump(Option<Suit>),
+    /// the player picking trump will play alone, see [`crate::contract::Contract::Solo`]
+    PickSolo,
+    /// the player picking trump will play alone, see [`crate::contract::Contract::Misere`]
+    PickMisere,
+    /// let the next player pick trump, see [`crate::round::Round::set_allow_pass`]
+    PassTrump,
     PlayCard(Card),
 }
 
@@ -14,6 +20,9 @@ impl Display for Action {
         match *self {
             Action::PickTrump(Some(suit)) => write!(f, "{suit}"),
             Action::PickTrump(None) => write!(f, "None"),
+            Action::PickSolo => write!(f, "Solo"),
+            Action::PickMisere => write!(f, "Misère"),
+            Action::PassTrump => write!(f, "Pass"),
             Action::PlayCard(card) => write!(f, "{card}"),
         }
     }
@@ -10,13 +10,23 @@ use crate::{
 };
 
 const NO_TRUMP_INDEX: u8 = 4;
-const NO_TRUMP_MASK: u8 = 1 << NO_TRUMP_INDEX;
+const SOLO_INDEX: u8 = 5;
+const MISERE_INDEX: u8 = 6;
+const PASS_INDEX: u8 = 7;
+
+/// the first byte of [`ActionCollection::to_bytes`]
+const UNINIT_TAG: u8 = 0;
+const CARDS_TAG: u8 = 1;
+const TRUMPS_TAG: u8 = 2;
 
 #[derive(Clone, Copy)]
 pub enum ActionCollection {
     Cards(Stack),
     /// bit 0..=3 are your regular suits
     /// bit 4 means without trump
+    /// bit 5 means playing solo
+    /// bit 6 means playing misère
+    /// bit 7 means passing to the next player
     Trumps(u8),
     Uninit,
 }
@@ -30,13 +40,7 @@ impl ActionCollection {
                 let mut bits = bits as u32;
                 while bits != 0 {
                     let idx = pop_lsb(&mut bits);
-                    let action = if idx == NO_TRUMP_INDEX as _ {
-                        None
-                    } else {
-                        Some(Suit::from(idx as u8))
-                    };
-
-                    actions.push(Action::PickTrump(action));
+                    actions.push(trump_action(idx as u8));
                 }
 
                 actions
@@ -44,6 +48,57 @@ impl ActionCollection {
             ActionCollection::Uninit => vec![],
         }
     }
+
+    /// a compact encoding for sending over the network: a tag byte followed by
+    /// the card bits in little endian for `Cards`, or the trump bits for `Trumps`
+    pub fn to_bytes(self) -> Vec<u8> {
+        match self {
+            ActionCollection::Cards(stack) => {
+                let mut bytes = vec![CARDS_TAG];
+                bytes.extend(stack.get_data().to_le_bytes());
+                bytes
+            }
+            ActionCollection::Trumps(bits) => vec![TRUMPS_TAG, bits],
+            ActionCollection::Uninit => vec![UNINIT_TAG],
+        }
+    }
+
+    /// the inverse of [`ActionCollection::to_bytes`], `None` if `bytes` is malformed
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [CARDS_TAG, data @ ..] => {
+                let mut stack = Stack::default();
+                stack.set_data(u32::from_le_bytes(data.try_into().ok()?));
+                Some(ActionCollection::Cards(stack))
+            }
+            &[TRUMPS_TAG, bits] => Some(ActionCollection::Trumps(bits)),
+            [UNINIT_TAG] => Some(ActionCollection::Uninit),
+            _ => None,
+        }
+    }
+}
+
+/// the action corresponding to bit `index` of [`ActionCollection::Trumps`]
+pub(crate) fn trump_action(index: u8) -> Action {
+    match index {
+        NO_TRUMP_INDEX => Action::PickTrump(None),
+        SOLO_INDEX => Action::PickSolo,
+        MISERE_INDEX => Action::PickMisere,
+        PASS_INDEX => Action::PassTrump,
+        _ => Action::PickTrump(Some(Suit::from(index))),
+    }
+}
+
+/// the bit of [`ActionCollection::Trumps`] corresponding to `action`
+pub(crate) fn trump_index(action: Action) -> u8 {
+    match action {
+        Action::PickTrump(Some(suit)) => suit as u8,
+        Action::PickTrump(None) => NO_TRUMP_INDEX,
+        Action::PickSolo => SOLO_INDEX,
+        Action::PickMisere => MISERE_INDEX,
+        Action::PassTrump => PASS_INDEX,
+        Action::PlayCard(_) => unreachable!(),
+    }
 }
 
 impl ActionList<Action> for ActionCollection {
@@ -56,25 +111,15 @@ impl ActionList<Action> for ActionCollection {
             (ActionCollection::Cards(ref mut stack), Action::PlayCard(card)) => {
                 *stack |= 1 << card.get_index()
             }
-            (ActionCollection::Trumps(bits), Action::PickTrump(trump)) => {
-                if let Some(suit) = trump {
-                    *bits |= 1 << suit as u8;
-                } else {
-                    *bits |= 1 << NO_TRUMP_INDEX;
-                }
+            (ActionCollection::Trumps(bits), action) => {
+                *bits |= 1 << trump_index(action);
             }
             (this @ ActionCollection::Uninit, Action::PlayCard(card)) => {
                 let stack = Stack::default() | 1 << card.get_index();
                 *this = ActionCollection::Cards(stack);
             }
-            (this @ ActionCollection::Uninit, Action::PickTrump(trump)) => {
-                let bits = if let Some(suit) = trump {
-                    1 << suit as u8
-                } else {
-                    1 << NO_TRUMP_INDEX
-                };
-
-                *this = ActionCollection::Trumps(bits);
+            (this @ ActionCollection::Uninit, action) => {
+                *this = ActionCollection::Trumps(1 << trump_index(action));
             }
             _ => unreachable!(),
         }
@@ -89,15 +134,9 @@ impl ActionList<Action> for ActionCollection {
                     None
                 } else {
                     let index = select_random_set_bit(*bits as _);
-                    let choice = if index == NO_TRUMP_INDEX as _ {
-                        None
-                    } else {
-                        Some(Suit::from(index as u8))
-                    };
-
                     *bits ^= 1 << index;
 
-                    Some(Action::PickTrump(choice))
+                    Some(trump_action(index as u8))
                 }
             }
         }
@@ -114,15 +153,9 @@ impl ActionList<Action> for ActionCollection {
     fn has(&self, item: &Action) -> bool {
         match (self, item) {
             (ActionCollection::Cards(stack), Action::PlayCard(card)) => stack.has_card(*card),
-            (ActionCollection::Trumps(bits), Action::PickTrump(trump)) => {
-                if let Some(suit) = trump {
-                    *bits & 1 << *suit as u8 != 0
-                } else {
-                    *bits & NO_TRUMP_MASK != 0
-                }
-            }
-            (ActionCollection::Uninit, _) => false,
-            _ => unreachable!(),
+            (ActionCollection::Trumps(_), Action::PlayCard(_)) => false,
+            (ActionCollection::Trumps(bits), action) => *bits & 1 << trump_index(*action) != 0,
+            _ => false,
         }
     }
 
@@ -144,19 +177,39 @@ impl Debug for ActionCollection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Cards(stack) => writeln!(f, "{stack:?}"),
-            Self::Trumps(bits) => {
-                let mut suits = [Suit::Spades, Suit::Clubs, Suit::Hearts, Suit::Diamonds]
-                    .into_iter()
-                    .filter(|&s| bits & 1 << s as u8 != 0)
-                    .map(Some)
-                    .collect::<Vec<_>>();
-                if bits & NO_TRUMP_MASK != 0 {
-                    suits.push(None);
-                }
-
-                writeln!(f, "{suits:?}")
-            }
+            Self::Trumps(_) => writeln!(f, "{:?}", self.to_vec()),
             Self::Uninit => writeln!(f, "Uninit"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ismcts::action_list::ActionList;
+
+    use super::ActionCollection;
+    use crate::{action::Action, stack::Stack};
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let cards = ActionCollection::Cards(Stack::from_slice(&[0, 7, 16, 31]));
+        let trumps = ActionCollection::Trumps(0b10110101);
+
+        for collection in [cards, trumps, ActionCollection::Uninit] {
+            let bytes = collection.to_bytes();
+            let parsed = ActionCollection::from_bytes(&bytes).unwrap();
+
+            assert_eq!(parsed.to_vec(), collection.to_vec());
+            assert_eq!(parsed.len(), collection.len());
+        }
+
+        assert_eq!(cards.to_bytes().len(), 5);
+        assert_eq!(trumps.to_bytes(), [2, 0b10110101]);
+        assert!(ActionCollection::from_bytes(&[1, 0, 0]).is_none());
+        assert!(ActionCollection::from_bytes(&[3, 0]).is_none());
+        assert!(ActionCollection::from_bytes(&[]).is_none());
+        assert!(ActionCollection::from_bytes(&trumps.to_bytes())
+            .unwrap()
+            .has(&Action::PassTrump));
+    }
+}
@@ -0,0 +1,197 @@
+use ismcts::state::State;
+
+use crate::{
+    action::Action,
+    inference::Inference,
+    rng::{self, SeededRng},
+    round::{Round, RoundPhase},
+    suit::Suit,
+};
+
+/// average reward for the team of `seat` over `samples` random playouts,
+/// each starting from a different determinization of the hidden cards
+pub fn estimate_hand_strength(state: &Round, seat: usize, samples: usize) -> f32 {
+    assert!(samples > 0);
+
+    let inference = Inference::default();
+    let mut total = 0.;
+
+    for _ in 0..samples {
+        let mut round = state.randomize(seat, &inference);
+        round.do_rollout();
+        total += round.reward(seat);
+    }
+
+    total / samples as f32
+}
+
+/// the estimated hand strength of the player picking trump for each of their options
+pub fn evaluate_trumps(state: &Round, samples: usize) -> Vec<(Action, f32)> {
+    let seat = state.turn();
+
+    state
+        .possible_actions()
+        .to_vec()
+        .into_iter()
+        .map(|action| {
+            let round = state.next_state(action);
+            (action, estimate_hand_strength(&round, seat, samples))
+        })
+        .collect()
+}
+
+/// the average reward for `observer` when picking trump `a` minus that when picking `b`.
+/// Both choices are played out on the same `samples` determinizations, with the same random
+/// moves, so the difference isn't drowned out by the luck of the deal
+pub fn compare_trumps(
+    state: &Round,
+    observer: usize,
+    a: Option<Suit>,
+    b: Option<Suit>,
+    samples: usize,
+) -> f32 {
+    assert!(state.phase() == RoundPhase::PickTrump);
+    assert!(state.is_legal(&Action::PickTrump(a)) && state.is_legal(&Action::PickTrump(b)));
+    assert!(samples > 0);
+
+    let mut total = 0.;
+
+    for world in state.sample_worlds(observer, samples) {
+        let seed = rng::u64();
+        let [reward_a, reward_b] = [a, b].map(|trump| {
+            let mut round = world;
+            round.apply_action(Action::PickTrump(trump));
+            round.playout_with(&mut SeededRng::new(seed), observer)
+        });
+
+        total += reward_a - reward_b;
+    }
+
+    total / samples as f32
+}
+
+/// how much of the deal is still hidden from `observer`: the base 2 log of the number of
+/// ways to deal the cards they haven't seen to the other players, respecting the number
+/// of cards each of them holds and the suits they're known to be void in
+pub fn remaining_hand_entropy(state: &Round, observer: usize) -> f64 {
+    let others: [usize; 3] = std::array::from_fn(|i| (observer + i + 1) % 4);
+    let sizes = others.map(|i| state.player_cards(i).len() as usize);
+    let unseen = state.unplayed_cards() & !state.player_cards(observer);
+
+    // ways[a][b] is the number of ways to deal the suits so far, such that the
+    // first two others get `a` and `b` cards, and the third one the rest
+    let mut ways = vec![vec![0f64; sizes[1] + 1]; sizes[0] + 1];
+    ways[0][0] = 1.;
+    let mut num_dealt = 0;
+
+    for (suit, cards) in Suit::ALL.into_iter().zip(unseen.by_suit()) {
+        let n = cards.len() as usize;
+        let can_hold = others.map(|i| !state.is_void(i, suit));
+        let mut next = vec![vec![0f64; sizes[1] + 1]; sizes[0] + 1];
+
+        for a in 0..=sizes[0] {
+            for b in 0..=sizes[1] {
+                if ways[a][b] == 0. {
+                    continue;
+                }
+
+                for x in 0..=n.min(sizes[0] - a) {
+                    for y in 0..=(n - x).min(sizes[1] - b) {
+                        let counts = [x, y, n - x - y];
+                        let third = num_dealt - a - b + counts[2];
+                        let respects_voids = (0..3).all(|i| can_hold[i] || counts[i] == 0);
+
+                        if respects_voids && third <= sizes[2] {
+                            next[a + x][b + y] += ways[a][b] * multinomial(n, counts);
+                        }
+                    }
+                }
+            }
+        }
+
+        ways = next;
+        num_dealt += n;
+    }
+
+    ways[sizes[0]][sizes[1]].max(1.).log2()
+}
+
+/// n! / (k0! k1! k2!)
+fn multinomial(n: usize, k: [usize; 3]) -> f64 {
+    let factorial = |n: usize| (1..=n).map(|i| i as f64).product::<f64>();
+    factorial(n) / k.iter().map(|&k| factorial(k)).product::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::{action_list::ActionList, state::State};
+
+    use super::{compare_trumps, estimate_hand_strength, remaining_hand_entropy};
+    use crate::{
+        action::Action,
+        rng,
+        round::{Round, RoundPhase},
+        stack::{Stack, HEARTS, SEVENS},
+        suit::Suit,
+    };
+
+    #[test]
+    fn test_strong_hand_scores_higher() {
+        rng::seed_from(1);
+
+        let round = |hand: Stack| {
+            Round::from_observer(
+                hand,
+                Stack::default(),
+                [8; 4],
+                3,
+                0,
+                RoundPhase::PlayCards,
+                Some(Suit::Hearts),
+                [0; 2],
+            )
+        };
+
+        let strong = round(Stack::default() | HEARTS);
+        // the sevens and eights of every suit except trump, plus two nines
+        let weak = round(Stack::default() | (SEVENS | SEVENS << 1) & !HEARTS | 1 << 2 | 1 << 10);
+        assert_eq!(weak.player_cards(0).len(), 8);
+
+        let strong_score = estimate_hand_strength(&strong, 0, 200);
+        let weak_score = estimate_hand_strength(&weak, 0, 200);
+
+        assert!(strong_score > weak_score);
+    }
+
+    #[test]
+    fn test_compare_trumps() {
+        // the dealer, who picks trump, holds every heart, so can only choose
+        // between hearts and playing without trump
+        let round = Round::new_with_hand(0, 0, Stack::default() | HEARTS);
+        assert_eq!(round.legal_trumps(), vec![Some(Suit::Hearts), None]);
+
+        let hearts = Some(Suit::Hearts);
+        assert_eq!(compare_trumps(&round, 0, hearts, hearts, 50), 0.);
+        assert!(compare_trumps(&round, 0, hearts, None, 100) > 0.);
+    }
+
+    #[test]
+    fn test_remaining_hand_entropy() {
+        let mut round = Round::new(0);
+        round.apply_action(Action::PickTrump(None));
+
+        // 24! / (8!)^3 ways to deal the other hands
+        let start = remaining_hand_entropy(&round, 0);
+        assert!((start - 9_465_511_770f64.log2()).abs() < 1e-6);
+
+        let mut prev = start;
+        while !round.is_terminal() {
+            round.apply_action(round.possible_actions().pop_random().unwrap());
+
+            let entropy = remaining_hand_entropy(&round, 0);
+            assert!(entropy <= prev + 1e-9);
+            prev = entropy;
+        }
+        assert_eq!(prev, 0.);
+    }
+}
@@ -58,8 +58,21 @@ impl<T: Copy + Default, const N: usize> Array<T, N> {
     pub const fn len(&self) -> usize {
         self.index
     }
+
+    pub const fn is_empty(&self) -> bool {
+        self.index == 0
+    }
+}
+
+/// only compares the elements up to the length of both arrays
+impl<T: Copy + Default + PartialEq, const N: usize> PartialEq for Array<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
 }
 
+impl<T: Copy + Default + Eq, const N: usize> Eq for Array<T, N> {}
+
 impl<Idx, T: Copy + Default, const N: usize> Index<Idx> for Array<T, N>
 where
     Idx: SliceIndex<[T]>,
@@ -1,16 +1,337 @@
-use std::time::Instant;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use ismcts::{action_list::ActionList, searcher::Searcher, state::State};
 
 use crate::{
+    action::Action,
     game::Game,
-    players::{random_player::RandomPlayer, Player, PlayerVec},
+    history::RoundHistory,
+    inference::Inference,
+    players::{mcts_player::MctsPlayer, random_player::RandomPlayer, Player, PlayerVec},
+    rng,
+    round::Round,
+    tournament::{tournament, BotConfig},
 };
 
 const BENCH_SIZE: usize = 800_000;
+/// games for the search bench, which is a lot slower
+const MCTS_BENCH_SIZE: usize = 4;
+/// think time in ms of the players in the search bench
+const MCTS_THINK_TIME: u128 = 10;
+/// sequential searches reusing the same searcher
+const REUSE_BENCH_SIZE: usize = 1000;
+
+/// number of allocations so far, see [`CountingAllocator`]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// the system allocator, but counting every allocation so [`bench_search`] can report them.
+/// Only counts when installed as the `#[global_allocator]` of the binary
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// the results of many games between the same two teams
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct MatchStats {
+    pub wins: [usize; 2],
+    pub draws: usize,
+    /// sum of the score differences from the perspective of team 0
+    total_margin: i64,
+}
+
+impl MatchStats {
+    /// record a finished game with these final scores
+    pub fn record(&mut self, scores: [i16; 2]) {
+        match scores[0].cmp(&scores[1]) {
+            std::cmp::Ordering::Greater => self.wins[0] += 1,
+            std::cmp::Ordering::Less => self.wins[1] += 1,
+            std::cmp::Ordering::Equal => self.draws += 1,
+        }
+
+        self.total_margin += (scores[0] - scores[1]) as i64;
+    }
+
+    pub const fn num_games(&self) -> usize {
+        self.wins[0] + self.wins[1] + self.draws
+    }
+
+    pub const fn losses(&self, team: usize) -> usize {
+        self.wins[1 - team]
+    }
+
+    /// the average score difference in favour of `team`
+    pub fn avg_margin(&self, team: usize) -> f64 {
+        let margin = self.total_margin as f64 / self.num_games().max(1) as f64;
+        if team == 0 {
+            margin
+        } else {
+            -margin
+        }
+    }
+
+    /// the same results from the perspective of the other team
+    pub const fn mirrored(&self) -> Self {
+        MatchStats {
+            wins: [self.wins[1], self.wins[0]],
+            draws: self.draws,
+            total_margin: -self.total_margin,
+        }
+    }
+
+    /// the win rate of `team`, counting draws as half a win,
+    /// and its 95% confidence interval
+    pub fn win_rate(&self, team: usize) -> (f64, (f64, f64)) {
+        const Z: f64 = 1.96;
+
+        let n = self.num_games().max(1) as f64;
+        let rate = (self.wins[team] as f64 + self.draws as f64 / 2.) / n;
+        let sigma = (rate * (1. - rate) / n).sqrt();
+
+        (rate, (rate - Z * sigma, rate + Z * sigma))
+    }
+}
+
+impl Display for MatchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for team in 0..2 {
+            let (rate, (low, high)) = self.win_rate(team);
+            writeln!(
+                f,
+                "team {team}: {}W {}L {}D, avg margin {:.2}, win rate {:.1}% [{:.1}%, {:.1}%]",
+                self.wins[team],
+                self.losses(team),
+                self.draws,
+                self.avg_margin(team),
+                rate * 100.,
+                low * 100.,
+                high * 100.
+            )?;
+        }
+
+        Ok(())
+    }
+}
 
 pub fn bench(size: Option<usize>) {
     let size = size.unwrap_or(BENCH_SIZE);
 
     start_simple_bench::<RandomPlayer>(size, "random");
+    bench_snapshot(size);
+    bench_history(size);
+
+    let player_gen = || -> PlayerVec {
+        (0..4)
+            .map(|_| -> Box<dyn Player> { Box::new(MctsPlayer::new(MCTS_THINK_TIME, true)) })
+            .collect()
+    };
+    run_bench(MCTS_BENCH_SIZE, "mcts", false, player_gen);
+
+    bench_searcher_reuse(REUSE_BENCH_SIZE);
+}
+
+/// run `num_searches` short searches from different states with a single searcher,
+/// and check that nothing of an earlier search leaks into the statistics of the next
+fn bench_searcher_reuse(num_searches: usize) {
+    let mut searcher = Searcher::default();
+    let inference = Inference::default();
+    let mut round = Round::new(0);
+
+    let start = Instant::now();
+    for _ in 0..num_searches {
+        if round.is_terminal() {
+            round.setup_for_next_round();
+        }
+
+        let result = searcher.search(&round, &inference, 0);
+        let legal = round.possible_actions();
+
+        // every iteration passes through exactly one child of the root
+        let visits = result
+            .child_stats
+            .iter()
+            .map(|(s, _)| s.num_sims)
+            .sum::<usize>();
+        assert_eq!(visits, result.num_simulations);
+        assert!(result.child_stats.len() <= legal.len());
+        assert!(result.child_stats.iter().all(|(_, a)| legal.has(a)));
+
+        round.apply_action(result.best_action.unwrap());
+    }
+
+    println!(
+        "reused searcher:\t{num_searches} searches in {}ms",
+        start.elapsed().as_millis()
+    );
+}
+
+/// compare copying a whole round to taking and restoring a snapshot
+fn bench_snapshot(size: usize) {
+    let mut round = Round::new(0);
+    for _ in 0..10 {
+        round.apply_action(round.possible_actions().pop_random().unwrap());
+    }
+
+    let start = Instant::now();
+    for _ in 0..size {
+        let mut copy = black_box(round);
+        black_box(&mut copy);
+    }
+    let copy_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut restored = round;
+    for _ in 0..size {
+        let snapshot = black_box(round.snapshot());
+        restored.restore(&snapshot);
+        black_box(&mut restored);
+    }
+    let snapshot_time = start.elapsed();
+
+    println!("copy:\t{}ns", copy_time.as_nanos() / size as u128);
+    println!("snapshot:\t{}ns", snapshot_time.as_nanos() / size as u128);
+}
+
+/// compare copying the round the search works on to cloning it along with its history,
+/// which every search iteration would pay for if the history were part of the round
+fn bench_history(size: usize) {
+    let mut round = Round::new(0);
+    let mut history = RoundHistory::new(round);
+    for _ in 0..10 {
+        let action = round.possible_actions().pop_random().unwrap();
+        round.apply_action(action);
+        history.record(action);
+    }
+
+    let start = Instant::now();
+    for _ in 0..size {
+        let mut copy = black_box(round);
+        black_box(&mut copy);
+    }
+    let round_time = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..size {
+        let mut copy = black_box((round, history.clone()));
+        black_box(&mut copy);
+    }
+    let history_time = start.elapsed();
+
+    println!(
+        "round ({} bytes):\t{}ns",
+        size_of::<Round>(),
+        round_time.as_nanos() / size as u128
+    );
+    println!(
+        "round with history:\t{}ns",
+        history_time.as_nanos() / size as u128
+    );
+}
+
+/// run the same flat monte carlo search twice, once copying the root state
+/// for every playout and once restoring a single state from a snapshot
+pub fn bench_search(playouts: usize) {
+    let mut root = Round::new(0);
+    root.apply_action(root.possible_actions().pop_random().unwrap());
+
+    let seed = rng::split(1, 0);
+    println!("Searching with {playouts} playouts per action...");
+
+    rng::seed_from(seed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let by_copy = flat_search(&root, playouts, |root, action| {
+        let mut round = *root;
+        round.apply_action(action);
+        round.do_rollout();
+        round.reward(root.turn())
+    });
+    let copy_time = start.elapsed();
+    let copy_allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    rng::seed_from(seed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let snapshot = root.snapshot();
+    let mut round = root;
+    let by_snapshot = flat_search(&root, playouts, |root, action| {
+        round.apply_action(action);
+        round.do_rollout();
+        let reward = round.reward(root.turn());
+        round.restore(&snapshot);
+        reward
+    });
+    let snapshot_time = start.elapsed();
+    let snapshot_allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    assert_eq!(by_copy, by_snapshot);
+
+    let iterations = by_copy.1 as f64;
+    println!(
+        "copy:\t{:.0} playouts/s, {copy_allocations} allocations",
+        iterations / copy_time.as_secs_f64()
+    );
+    println!(
+        "snapshot:\t{:.0} playouts/s, {snapshot_allocations} allocations",
+        iterations / snapshot_time.as_secs_f64()
+    );
+    println!(
+        "speedup: {:.2}",
+        copy_time.as_secs_f64() / snapshot_time.as_secs_f64()
+    );
+}
+
+/// play searches with greedy rollouts against searches with random rollouts,
+/// with both teams getting the same cards, see [`tournament`]
+pub fn bench_rollouts(games: usize) {
+    let configs = [true, false].map(|greedy_rollout| BotConfig::Mcts {
+        search_time: MCTS_THINK_TIME,
+        use_inference: true,
+        greedy_rollout,
+    });
+
+    println!("Playing {games} paired games, greedy rollouts as team 0 against random rollouts...");
+    let results = tournament(&configs, games, 1);
+    print!("{}", results[0][1]);
+}
+
+/// the action with the highest average reward and the total number of playouts
+fn flat_search(
+    root: &Round,
+    playouts: usize,
+    mut playout: impl FnMut(&Round, Action) -> f32,
+) -> (Action, usize) {
+    let actions = root.possible_actions().to_vec();
+    let best = actions
+        .iter()
+        .map(|&action| {
+            let total = (0..playouts).map(|_| playout(root, action)).sum::<f32>();
+            (action, total)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+        .0;
+
+    (best, actions.len() * playouts)
 }
 
 fn start_simple_bench<T: Player + Default + 'static>(size: usize, name: &str) {
@@ -23,8 +344,8 @@ fn run_bench(
     name: &str,
     verbose: bool,
     player_gen: impl Fn() -> PlayerVec,
-) -> [i32; 2] {
-    println!("Simulating {size} random games for {name}...");
+) -> MatchStats {
+    println!("Simulating {size} games for {name}...");
 
     let mut games = Vec::with_capacity(size);
 
@@ -34,6 +355,9 @@ fn run_bench(
 
     let start = Instant::now();
     let mut total_rounds = 0;
+    let mut total_decisions = 0;
+    let mut total_choices = 0;
+    let mut total_simulations = 0;
 
     for game in &mut games {
         while !game.is_terminal() {
@@ -41,9 +365,12 @@ fn run_bench(
         }
 
         total_rounds += game.num_rounds();
+        total_decisions += game.num_decisions();
+        total_choices += game.num_choices();
+        total_simulations += game.num_simulations();
 
         if verbose {
-            println!("{}", game.winner());
+            println!("{:?}", game.winner());
         }
     }
 
@@ -53,11 +380,57 @@ fn run_bench(
         (size as f64) / start.elapsed().as_secs_f64()
     );
     println!("avg num of rounds: {}", total_rounds as f64 / size as f64);
+    println!(
+        "avg num of decisions: {}",
+        total_decisions as f64 / size as f64
+    );
+    if total_simulations > 0 {
+        println!(
+            "search iterations: {total_simulations} total, {} per game",
+            total_simulations as f64 / size as f64
+        );
+    }
+    println!(
+        "avg branching factor: {}",
+        total_choices as f64 / total_decisions as f64
+    );
 
-    let mut score = [0; 2];
-    for game in &mut games {
-        score[game.winner()] += 1;
+    let mut stats = MatchStats::default();
+    for game in &games {
+        stats.record(game.scores());
+    }
+    print!("{stats}");
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bench_searcher_reuse, MatchStats};
+
+    #[test]
+    fn test_searcher_reuse() {
+        bench_searcher_reuse(20);
     }
 
-    score
+    #[test]
+    fn test_match_stats() {
+        let mut stats = MatchStats::default();
+        for scores in [[61, 40], [30, 62], [65, 10], [61, 61]] {
+            stats.record(scores);
+        }
+
+        assert_eq!(stats.num_games(), 4);
+        assert_eq!(stats.wins, [2, 1]);
+        assert_eq!(stats.draws, 1);
+        assert_eq!(stats.losses(0), 1);
+        assert_eq!(stats.losses(1), 2);
+        assert_eq!(stats.avg_margin(0), 44. / 4.);
+        assert_eq!(stats.avg_margin(1), -44. / 4.);
+
+        let (rate, (low, high)) = stats.win_rate(0);
+        assert_eq!(rate, 2.5 / 4.);
+        assert!(low < rate && rate < high);
+        assert_eq!(stats.win_rate(1).0, 1. - rate);
+    }
 }
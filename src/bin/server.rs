@@ -0,0 +1,22 @@
+use std::net::TcpListener;
+
+use manille_master::{io::server::serve, rng};
+
+/// usage: server [port] [seat] [think time]
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let port = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(7878u16);
+    let seat = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
+    let think_time = args.get(3).and_then(|s| s.parse().ok()).unwrap_or(1000);
+    rng::seed();
+
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("waiting for a client on port {port}, playing as seat {seat}");
+
+    match serve(&listener, seat, think_time)? {
+        Some(team) => println!("team {team} wins the game"),
+        None => println!("the game is a draw"),
+    }
+
+    Ok(())
+}
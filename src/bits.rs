@@ -1,6 +1,8 @@
 #[cfg(feature = "bmi2")]
 use std::arch::x86_64::_pdep_u32;
 
+use crate::rng;
+
 pub const fn lsb(data: u32) -> u32 {
     data.trailing_zeros()
 }
@@ -25,7 +27,7 @@ pub fn pop_random_set_bit(data: &mut u32) -> u32 {
 
 #[cfg(not(feature = "bmi2"))]
 pub fn select_random_set_bit(mut data: u32) -> u32 {
-    let bit = romu::mod_u32(data.count_ones());
+    let bit = rng::mod_u32(data.count_ones());
     for _ in 0..bit {
         data &= data - 1;
     }
@@ -39,7 +41,7 @@ pub fn select_random_set_bit(mut data: u32) -> u32 {
 //
 #[cfg(feature = "bmi2")]
 pub fn select_random_set_bit(data: u32) -> u32 {
-    let bit = romu::mod_u32(data.count_ones());
+    let bit = rng::mod_u32(data.count_ones());
     let mask = 1 << bit;
 
     unsafe {
@@ -1,5 +1,5 @@
 use crate::suit::Suit;
-use std::fmt;
+use std::{cmp::Ordering, fmt};
 
 /// Some info on the following structures:
 /// card values range from 0..=7, with 0 being a seven, 1 an eight, ..., 7 a ten
@@ -41,6 +41,11 @@ impl Card {
         }
     }
 
+    /// the full deck, in index order
+    pub fn all() -> impl Iterator<Item = Card> {
+        (0..32).map(Card::new)
+    }
+
     pub const fn get_index(&self) -> u32 {
         self.index as _
     }
@@ -62,6 +67,18 @@ impl Card {
         self.suit
     }
 
+    /// whether this card beats `other` in a trick where `led` was the first suit played.
+    /// Note that this differs from [`Ord`], which only orders cards by suit and value
+    pub fn beats(&self, other: &Card, led: Suit, trump: Option<Suit>) -> bool {
+        if self.suit == other.suit {
+            self.value > other.value
+        } else if trump.is_some_and(|t| t == self.suit || t == other.suit) {
+            trump == Some(self.suit)
+        } else {
+            self.suit == led
+        }
+    }
+
     pub const fn score(&self) -> i16 {
         match self.value() as i16 {
             0..=2 => 0,
@@ -71,6 +88,19 @@ impl Card {
     }
 }
 
+/// orders cards by suit first and then by value, useful for displaying cards
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut result = self.suit.to_string();
@@ -100,6 +130,7 @@ impl fmt::Debug for Card {
 #[cfg(test)]
 mod tests {
     use super::Card;
+    use crate::{stack::Stack, suit::Suit};
 
     #[test]
     fn test_to_index() {
@@ -107,4 +138,42 @@ mod tests {
         assert!(Card::new(4).get_index() == 4);
         assert!(Card::new(17).get_index() == 17);
     }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(Card::all().count(), 32);
+        assert!(Card::all().collect::<Stack>() == Stack::ALL);
+        assert!(Card::all().enumerate().all(|(i, c)| c.get_index() == i as u32));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut cards = vec![Card::new(31), Card::new(3), Card::new(7), Card::new(12)];
+        cards.sort();
+
+        assert_eq!(
+            cards,
+            vec![Card::new(3), Card::new(7), Card::new(12), Card::new(31)]
+        );
+        // a ten of spades sorts before a seven of clubs
+        assert!(Card::new(7) < Card::new(8));
+    }
+
+    #[test]
+    fn test_beats() {
+        let seven_of_spades = Card::new(0);
+        let ten_of_spades = Card::new(7);
+        let ace_of_clubs = Card::new(14);
+        let eight_of_hearts = Card::new(17);
+
+        // higher card of the same suit
+        assert!(ten_of_spades.beats(&seven_of_spades, Suit::Spades, None));
+        assert!(!seven_of_spades.beats(&ten_of_spades, Suit::Spades, None));
+
+        // not following loses, unless it's a trump
+        assert!(!ace_of_clubs.beats(&seven_of_spades, Suit::Spades, None));
+        assert!(!ace_of_clubs.beats(&seven_of_spades, Suit::Spades, Some(Suit::Hearts)));
+        assert!(eight_of_hearts.beats(&ten_of_spades, Suit::Spades, Some(Suit::Hearts)));
+        assert!(!ten_of_spades.beats(&eight_of_hearts, Suit::Spades, Some(Suit::Hearts)));
+    }
 }
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use crate::suit::Suit;
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Contract {
+    Trump(Suit),
+    #[default]
+    NoTrump,
+    /// the declarer plays alone against the other three players,
+    /// and has to take every single trick. This is played without trump
+    Solo {
+        declarer: usize,
+    },
+    /// the declarer plays alone against the other three players,
+    /// and mustn't take a single trick. This is played without trump
+    Misere {
+        declarer: usize,
+    },
+}
+
+impl Contract {
+    pub const fn trump(&self) -> Option<Suit> {
+        match *self {
+            Contract::Trump(suit) => Some(suit),
+            _ => None,
+        }
+    }
+
+    pub const fn declarer(&self) -> Option<usize> {
+        match *self {
+            Contract::Solo { declarer } | Contract::Misere { declarer } => Some(declarer),
+            _ => None,
+        }
+    }
+}
+
+impl From<Option<Suit>> for Contract {
+    fn from(trump: Option<Suit>) -> Self {
+        match trump {
+            Some(suit) => Contract::Trump(suit),
+            None => Contract::NoTrump,
+        }
+    }
+}
+
+impl Display for Contract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Contract::Trump(suit) => write!(f, "{suit}"),
+            Contract::NoTrump => write!(f, "None"),
+            Contract::Solo { declarer } => write!(f, "Solo ({declarer})"),
+            Contract::Misere { declarer } => write!(f, "Misère ({declarer})"),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::round::Round;
+
+/// worth of a card that is the highest remaining of its suit, in card points
+const BOSS_BONUS: f32 = 4.;
+/// worth of every trump in hand, in card points
+const TRUMP_BONUS: f32 = 3.;
+
+/// static estimate in [-1, 1] of how well the round is going for the team of `perspective`,
+/// without playing anything out. Like [`Round::heuristic_reward`] it starts from the margin
+/// in card points, but also credits boss cards and trump length of both teams.
+/// A solo or misère isn't decided by card points, so those fall back to
+/// [`Round::heuristic_reward`], as does a deal without card points
+pub fn evaluate(state: &Round, perspective: usize) -> f32 {
+    if state.contract().declarer().is_some() || state.points_in_play() == 0 {
+        return state.heuristic_reward(perspective);
+    }
+
+    let team = state.side_of(perspective);
+    let scores = state.scores();
+    let mut value = [scores[0] as f32, scores[1] as f32];
+
+    for seat in 0..4 {
+        let cards = state.player_cards(seat);
+        let mut bonus = state.boss_cards(seat).len() as f32 * BOSS_BONUS;
+        if let Some(trump) = state.trump() {
+            bonus += cards.of_suit(trump).len() as f32 * TRUMP_BONUS;
+        }
+
+        value[state.side_of(seat)] += bonus;
+    }
+
+    ((value[team] - value[1 - team]) / state.points_in_play() as f32).clamp(-1., 1.)
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::state::State;
+
+    use super::evaluate;
+    use crate::{action::Action, round::Round, stack::Stack, suit::Suit};
+
+    #[test]
+    fn test_trump_holding() {
+        // every player holds a single suit, so only the choice of trump differs
+        let hands = std::array::from_fn(|i| Stack::ALL.of_suit(Suit::from(i as u8)));
+        let round = Round::from_deal(3, hands);
+
+        let strong = round.next_state(Action::PickTrump(Some(Suit::Spades)));
+        let weak = round.next_state(Action::PickTrump(Some(Suit::Clubs)));
+
+        assert!(evaluate(&strong, 0) > evaluate(&weak, 0));
+        assert!(evaluate(&strong, 0) > 0.);
+        assert_eq!(evaluate(&strong, 0), -evaluate(&strong, 1));
+    }
+
+    #[test]
+    fn test_zero_point_deal() {
+        let hands = [0, 8, 16, 24].map(|card| Stack::from_slice(&[card]));
+        let round = Round::from_deal(3, hands).next_state(Action::PickTrump(Some(Suit::Spades)));
+
+        assert_eq!(evaluate(&round, 0), 0.);
+    }
+}
@@ -1,79 +1,289 @@
-use std::fmt::Debug;
+use std::{
+    cmp::Ordering,
+    fmt::{Arguments, Debug, Display},
+    io::Write,
+};
 
 use ismcts::{action_list::ActionList, state::State};
 
-use crate::{action::Action, inference::Inference, players::PlayerVec, round::Round, stack::Stack};
+use crate::{
+    action::Action,
+    action_collection::ActionCollection,
+    history::RoundHistory,
+    inference::Inference,
+    players::PlayerVec,
+    rng,
+    round::{partner_of, Round, RoundPhase},
+    stack::Stack,
+    trick::Trick,
+};
 
 const MAX_SCORE: i16 = 61;
 
+/// a player tried to make a move that isn't allowed, see [`Game::play_round_checked`]
+#[derive(Debug)]
+pub struct IllegalMove {
+    pub seat: usize,
+    pub action: Action,
+    pub legal: ActionCollection,
+}
+
+impl Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "player {} tried to play {}, legal actions are {:?}",
+            self.seat, self.action, self.legal
+        )
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// what a [`Game`] prints while playing
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verbosity {
+    #[default]
+    Silent,
+    /// every action
+    Moves,
+    /// the result of each trick and round
+    Tricks,
+    /// all of the above
+    Full,
+}
+
+impl Verbosity {
+    fn includes(self, level: Verbosity) -> bool {
+        self == Verbosity::Full || self == level
+    }
+}
+
 #[derive(Default)]
 pub struct Game {
     players: PlayerVec,
     round: Round,
+    /// how the current round was played so far, `None` when the game started
+    /// from a round it didn't deal, see [`Game::from_state`]
+    history: Option<RoundHistory>,
     inference: Inference,
     num_rounds: usize,
     scores: [i16; 2],
+    num_decisions: usize,
+    /// total number of legal actions over all decisions
+    num_choices: usize,
+    verbosity: Verbosity,
+    /// where to log to, `None` prints to stdout
+    output: Option<Box<dyn Write>>,
+    /// whether to keep the notation of every finished round, see [`Game::set_record_notation`]
+    record_notation: bool,
+    /// the notation of every finished round, see [`RoundHistory::to_notation`].
+    /// Rounds without a history are left out
+    round_notations: Vec<String>,
 }
 
 impl Game {
     pub fn new(players: PlayerVec) -> Self {
-        let dealer = romu::range_usize(0..4);
+        Self::new_with_dealer(players, rng::range_usize(0..4))
+    }
+
+    pub fn new_with_dealer(players: PlayerVec, dealer: usize) -> Self {
+        let round = Round::new(dealer);
 
         Game {
             players,
-            round: Round::new(dealer),
+            round,
+            history: Some(RoundHistory::new(round)),
             inference: Default::default(),
             num_rounds: 0,
             scores: [0; 2],
+            num_decisions: 0,
+            num_choices: 0,
+            verbosity: Verbosity::Silent,
+            output: None,
+            record_notation: false,
+            round_notations: vec![],
+        }
+    }
+
+    /// the first round deals exactly `hand` to `seat`, e.g. to practice a specific hand.
+    /// Later rounds are dealt randomly
+    pub fn new_with_hand(players: PlayerVec, seat: usize, hand: Stack) -> Self {
+        let round = Round::new_with_hand(rng::range_usize(0..4), seat, hand);
+        let mut game = Self::from_state(players, round);
+        game.history = Some(RoundHistory::new(round));
+
+        game
+    }
+
+    /// seeds the generator of the current thread, see [`rng::split`]
+    /// to derive seeds for games running in parallel
+    pub fn new_seeded(players: PlayerVec, seed: u64) -> Self {
+        rng::seed_from(seed);
+        Self::new(players)
+    }
+
+    /// resume from an existing round, without dealing new cards. How that round was dealt
+    /// and played isn't known, so it has no history, see [`Game::trick_history`]
+    pub fn from_state(players: PlayerVec, round: Round) -> Self {
+        Game {
+            players,
+            round,
+            history: None,
+            inference: Default::default(),
+            num_rounds: 0,
+            scores: [0; 2],
+            num_decisions: 0,
+            num_choices: 0,
+            verbosity: Verbosity::Silent,
+            output: None,
+            record_notation: false,
+            round_notations: vec![],
+        }
+    }
+
+    pub const fn set_verbosity(&mut self, verbosity: Verbosity) {
+        self.verbosity = verbosity;
+    }
+
+    /// keep the notation of every finished round from now on, see [`Game::notation`].
+    /// Off by default, as writing it down replays the whole round
+    pub const fn set_record_notation(&mut self, record: bool) {
+        self.record_notation = record;
+    }
+
+    /// log to `output` instead of stdout
+    pub fn set_output(&mut self, output: Box<dyn Write>) {
+        self.output = Some(output);
+    }
+
+    fn log(&mut self, level: Verbosity, message: Arguments) {
+        if !self.verbosity.includes(level) {
+            return;
+        }
+
+        match &mut self.output {
+            Some(output) => writeln!(output, "{message}").unwrap(),
+            None => println!("{message}"),
         }
     }
 
     fn apply_action(&mut self, action: Action) {
+        self.num_decisions += 1;
+        self.num_choices += self.legal_actions().len();
+
+        let turn = self.round.turn();
+        self.log(
+            Verbosity::Moves,
+            format_args!("player {turn} plays {action}"),
+        );
+
         self.inference.infer(&self.round, action, self.round.turn());
         self.round.apply_action(action);
+        if let Some(history) = &mut self.history {
+            history.record(action);
+        }
     }
 
-    fn play_trick(&mut self) {
-        let turn = self.round.turn();
+    /// ask the player to move, when `checked` an illegal action
+    /// results in an error instead of a debug assertion
+    fn decide(&mut self, checked: bool) -> Result<Action, IllegalMove> {
+        let seat = self.round.turn();
+        let action = self.players[seat].decide(self.round, &self.inference);
+
+        if checked && !self.is_legal(action) {
+            return Err(IllegalMove {
+                seat,
+                action,
+                legal: self.legal_actions(),
+            });
+        }
 
-        for i in turn..(turn + 4) {
-            let player_idx = i % 4;
-            let action = self.players[player_idx].decide(self.round, &self.inference);
+        debug_assert!(self.is_legal(action));
 
-            match action {
-                Action::PlayCard(_) => {
-                    debug_assert!(self.is_legal(action));
+        Ok(action)
+    }
 
-                    self.apply_action(action);
-                }
+    /// play the remaining cards of the current trick
+    fn play_trick(&mut self, checked: bool) -> Result<(), IllegalMove> {
+        let num_played = self.round.cards_in_current_trick();
+        let prev_scores = self.round.scores();
+
+        for seat in self.round.trick_order().into_iter().skip(num_played) {
+            debug_assert_eq!(seat, self.round.turn());
+            let action = self.decide(checked)?;
+
+            match action {
+                Action::PlayCard(_) => self.apply_action(action),
                 _ => unreachable!(),
             }
         }
+
+        // the winner of a trick leads the next one
+        let winner = self.round.turn();
+        let points = self.round.scores().iter().sum::<i16>() - prev_scores.iter().sum::<i16>();
+        self.log(
+            Verbosity::Tricks,
+            format_args!("player {winner} wins the trick for {points} points\n"),
+        );
+
+        Ok(())
     }
 
-    /// play an entire round, i.e. 8 tricks
+    /// play the rest of the current round, i.e. up to 8 tricks, and deal the next one
     pub fn play_round(&mut self) {
-        self.inference = Inference::default();
-        self.round.setup_for_next_round();
+        self.play_round_inner(false).unwrap();
+    }
 
-        let action = self.players[self.round.turn()].decide(self.round, &self.inference);
-        self.apply_action(action);
+    /// like [`Game::play_round`], but returns an error as soon as a player
+    /// makes an illegal move, leaving the game in the state before that move
+    pub fn play_round_checked(&mut self) -> Result<(), IllegalMove> {
+        self.play_round_inner(true)
+    }
+
+    fn play_round_inner(&mut self, checked: bool) -> Result<(), IllegalMove> {
+        while self.round.phase() == RoundPhase::PickTrump {
+            let action = self.decide(checked)?;
+            self.apply_action(action);
+        }
 
-        for _ in 0..8 {
-            self.play_trick();
+        while !self.round.is_terminal() {
+            self.play_trick(checked)?;
         }
 
         let scores = self.round.scores();
-        let winning_team = if scores[0] > scores[1] { 0 } else { 1 };
-        self.scores[winning_team] += scores[winning_team] - 30;
+        let (winning_team, points) = self.round.result();
+        self.scores[winning_team] += points;
 
-        assert!(scores.iter().sum::<i16>() == 60);
+        assert!(scores.iter().sum::<i16>() == self.round.points_in_play());
         self.num_rounds += 1;
+
+        let total = self.scores;
+        self.log(
+            Verbosity::Tricks,
+            format_args!(
+                "team {winning_team} wins the round for {points} points, round score: {scores:?}"
+            ),
+        );
+        self.log(Verbosity::Tricks, format_args!("total score: {total:?}\n"));
+
+        if self.record_notation {
+            let notation = self.history.as_ref().and_then(RoundHistory::to_notation);
+            self.round_notations.extend(notation);
+        }
+        self.inference = Inference::default();
+        self.round.setup_for_next_round();
+        match &mut self.history {
+            Some(history) => history.restart(self.round),
+            None => self.history = Some(RoundHistory::new(self.round)),
+        }
+
+        Ok(())
     }
 
     /// controleer of deze speler al dan niet kan volgen
     pub fn is_legal(&self, action: Action) -> bool {
-        self.legal_actions().has(&action)
+        self.round.is_legal(&action)
     }
 
     pub fn legal_actions(&self) -> <Round as State>::ActionList {
@@ -84,23 +294,66 @@ impl Game {
         self.round.player_cards(player)
     }
 
+    /// the finished tricks of the current round, `None` when the game started from a
+    /// round it didn't deal, see [`RoundHistory::trick_history`]
+    pub fn trick_history(&self) -> Option<Vec<Trick>> {
+        self.history.as_ref().map(RoundHistory::trick_history)
+    }
+
+    /// how the current round was played so far, see [`Game::trick_history`]
+    pub const fn history(&self) -> Option<&RoundHistory> {
+        self.history.as_ref()
+    }
+
     pub const fn num_rounds(&self) -> usize {
         self.num_rounds
     }
 
+    pub const fn num_decisions(&self) -> usize {
+        self.num_decisions
+    }
+
+    /// total number of legal actions over all decisions
+    pub const fn num_choices(&self) -> usize {
+        self.num_choices
+    }
+
+    /// the game score of both teams
+    pub const fn scores(&self) -> [i16; 2] {
+        self.scores
+    }
+
+    /// the notation of every round played so far, one per line, skipping the rounds that
+    /// can't be written down. Empty unless enabled with [`Game::set_record_notation`]
+    pub fn notation(&self) -> String {
+        self.round_notations.join("\n")
+    }
+
+    /// search iterations of all players so far, see [`crate::players::Player::num_simulations`]
+    pub fn num_simulations(&self) -> usize {
+        self.players.iter().map(|p| p.num_simulations()).sum()
+    }
+
     pub fn is_terminal(&self) -> bool {
         self.scores.iter().any(|&s| s >= MAX_SCORE)
     }
 
-    pub fn winner(&self) -> usize {
+    /// the team that won the game, i.e. 0 for seats 0 and 2, and 1 for seats 1 and 3,
+    /// `None` if both teams ended on the same score
+    pub fn winner(&self) -> Option<usize> {
         assert!(self.is_terminal());
 
-        self.scores
-            .iter()
-            .enumerate()
-            .max_by_key(|(_, s)| **s)
-            .unwrap()
-            .0
+        match self.scores[0].cmp(&self.scores[1]) {
+            Ordering::Greater => Some(0),
+            Ordering::Less => Some(1),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// the seats of the team that won the game, `None` on a draw
+    pub fn winning_seats(&self) -> Option<[usize; 2]> {
+        let team = self.winner()?;
+        Some([team, partner_of(team)])
     }
 }
 
@@ -114,12 +367,49 @@ impl Debug for Game {
 
 #[cfg(test)]
 mod tests {
-    use super::Game;
+    use ismcts::{action_list::ActionList, state::State};
+
+    use std::{cell::RefCell, io::Write, rc::Rc};
+
+    use super::{Game, Verbosity};
     use crate::{
+        action::Action,
+        inference::Inference,
         players::{random_player::RandomPlayer, Player, PlayerVec},
+        rng,
+        round::{Round, RoundPhase},
         stack::Stack,
     };
 
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// plays a card from someone else's hand
+    struct CheatingPlayer;
+
+    impl Player for CheatingPlayer {
+        fn decide(&mut self, round: Round, _inference: &Inference) -> Action {
+            match round.phase() {
+                RoundPhase::PickTrump => Action::PickTrump(None),
+                RoundPhase::PlayCards => {
+                    let others = round.unplayed_cards() ^ round.cards_to_move();
+                    let card = others.into_iter().next().unwrap();
+                    Action::PlayCard(card)
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_dealing() {
         let players: PlayerVec = vec![
@@ -156,4 +446,218 @@ mod tests {
             game.play_round();
         }
     }
+
+    #[test]
+    fn test_seeded_dealing() {
+        let players = || -> PlayerVec { (0..4).map(|_| RandomPlayer::boxed() as _).collect() };
+        let deal = |seed| {
+            let game = Game::new_seeded(players(), seed);
+            std::array::from_fn::<_, 4, _>(|i| game.player_cards(i))
+        };
+
+        let a = deal(rng::split(7, 0));
+        let b = deal(rng::split(7, 1));
+
+        assert!(a == deal(rng::split(7, 0)));
+        assert!(b == deal(rng::split(7, 1)));
+        assert!(a != b);
+    }
+
+    #[test]
+    fn test_dealer() {
+        for dealer in 0..4 {
+            let players: PlayerVec = vec![
+                RandomPlayer::boxed(),
+                RandomPlayer::boxed(),
+                RandomPlayer::boxed(),
+                RandomPlayer::boxed(),
+            ];
+
+            let mut game = Game::new_with_dealer(players, dealer);
+            assert_eq!(game.round.dealer(), dealer);
+            assert_eq!(game.round.turn(), dealer);
+
+            game.round.apply_action(Action::PickTrump(None));
+            assert_eq!(game.round.turn(), (dealer + 1) % 4);
+        }
+    }
+
+    #[test]
+    fn test_from_state() {
+        let players: PlayerVec = vec![
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+        ];
+
+        let mut round = Round::new(1);
+        while round.played_cards().len() < 6 {
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+        }
+
+        let mut game = Game::from_state(players, round);
+        assert!(game.round.played_cards() == round.played_cards());
+        assert_eq!(game.round.scores(), round.scores());
+        for i in 0..4 {
+            assert!(game.player_cards(i) == round.player_cards(i));
+        }
+
+        game.play_round();
+        assert_eq!(game.num_rounds(), 1);
+    }
+
+    #[test]
+    fn test_play_round_checked() {
+        let players: PlayerVec = vec![
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+        ];
+        let mut game = Game::new(players);
+        for _ in 0..5 {
+            assert!(game.play_round_checked().is_ok());
+        }
+
+        let players: PlayerVec = vec![
+            RandomPlayer::boxed(),
+            Box::new(CheatingPlayer),
+            RandomPlayer::boxed(),
+            RandomPlayer::boxed(),
+        ];
+        let mut game = Game::new_with_dealer(players, 0);
+
+        let error = game.play_round_checked().unwrap_err();
+        assert_eq!(error.seat, 1);
+        assert!(!error.legal.to_vec().contains(&error.action));
+        assert!(!game.is_legal(error.action));
+    }
+
+    #[test]
+    fn test_verbosity() {
+        let output = |verbosity| {
+            let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+            let buffer = SharedBuffer::default();
+
+            let mut game = Game::new(players);
+            game.set_verbosity(verbosity);
+            game.set_output(Box::new(buffer.clone()));
+            game.play_round();
+
+            String::from_utf8(buffer.0.take()).unwrap()
+        };
+
+        assert!(output(Verbosity::Silent).is_empty());
+
+        let moves = output(Verbosity::Moves);
+        assert_eq!(moves.lines().count(), 33);
+        assert!(!moves.contains("wins"));
+
+        let tricks = output(Verbosity::Tricks);
+        assert!(tricks.contains("wins the trick") && tricks.contains("wins the round"));
+        assert!(!tricks.contains("plays"));
+
+        let full = output(Verbosity::Full);
+        assert!(full.contains("plays") && full.contains("wins the round"));
+    }
+
+    #[test]
+    fn test_winning_seats() {
+        let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+        let mut game = Game::new(players);
+        game.scores = [64, 41];
+
+        assert_eq!(game.winner(), Some(0));
+        assert_eq!(game.winning_seats(), Some([0, 2]));
+
+        game.scores = [52, 61];
+        assert_eq!(game.winning_seats(), Some([1, 3]));
+    }
+
+    #[test]
+    fn test_trick_history() {
+        let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+        let mut game = Game::new(players);
+        assert_eq!(game.trick_history(), Some(vec![]));
+
+        while game.round.phase() == RoundPhase::PickTrump {
+            let action = game.decide(false).unwrap();
+            game.apply_action(action);
+        }
+        for _ in 0..3 {
+            game.play_trick(false).unwrap();
+        }
+
+        assert_eq!(game.trick_history().unwrap().len(), 3);
+        assert_eq!(game.history().unwrap().current(), game.round);
+
+        let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+        let observed = Game::from_state(players, game.round.observed_by(0));
+        assert_eq!(observed.trick_history(), None);
+    }
+
+    #[test]
+    fn test_record_notation() {
+        let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+        let mut game = Game::new(players);
+
+        game.play_round();
+        assert!(game.notation().is_empty());
+
+        game.set_record_notation(true);
+        game.play_round();
+        assert_eq!(game.notation().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_notation_from_observer() {
+        let hand = Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let round = Round::from_observer(
+            hand,
+            Stack::default(),
+            [8; 4],
+            0,
+            1,
+            RoundPhase::PickTrump,
+            None,
+            [0; 2],
+        );
+
+        let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+        let mut game = Game::from_state(players, round);
+        game.set_record_notation(true);
+
+        // only the observer's hand is known, so the first round can't be written down
+        game.play_round();
+        assert!(game.notation().is_empty());
+
+        game.play_round();
+        assert_eq!(game.notation().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_draw() {
+        let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+        let mut game = Game::new(players);
+        game.scores = [101, 101];
+
+        assert!(game.is_terminal());
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.winning_seats(), None);
+    }
+
+    #[test]
+    fn test_new_with_hand() {
+        let hand = Stack::from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        for _ in 0..50 {
+            let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+            let mut game = Game::new_with_hand(players, 3, hand);
+            assert!(game.player_cards(3) == hand);
+
+            game.play_round();
+        }
+    }
 }
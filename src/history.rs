@@ -0,0 +1,443 @@
+//! how a round was played, kept next to the [`Round`] instead of inside it:
+//! the search copies a round on every iteration and never looks back
+
+use std::fmt::Write;
+
+use ismcts::state::State;
+
+use crate::{
+    action::Action,
+    action_collection::{trump_action, trump_index},
+    card::Card,
+    contract::Contract,
+    round::{Round, RoundPhase},
+    stack::Stack,
+    suit::Suit,
+    trick::Trick,
+};
+
+/// what changed between two states of the same round, see [`RoundHistory::diff`]:
+/// only the actions in between, everything else follows from replaying them
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateDelta {
+    actions: Vec<Action>,
+}
+
+/// [`StateDelta::to_bytes`] stores cards as their index,
+/// and the other actions as this plus their [`trump_index`]
+const DELTA_TRUMP_OFFSET: u8 = 32;
+
+impl StateDelta {
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// a compact encoding for sending over the network, one byte per action
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.actions
+            .iter()
+            .map(|&action| match action {
+                Action::PlayCard(card) => card.get_index() as u8,
+                _ => DELTA_TRUMP_OFFSET + trump_index(action),
+            })
+            .collect()
+    }
+
+    /// the inverse of [`StateDelta::to_bytes`], `None` if `bytes` is malformed
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let actions = bytes
+            .iter()
+            .map(|&byte| match byte {
+                0..DELTA_TRUMP_OFFSET => Some(Action::PlayCard(Card::new(byte as u32))),
+                _ if byte - DELTA_TRUMP_OFFSET <= 7 => {
+                    Some(trump_action(byte - DELTA_TRUMP_OFFSET))
+                }
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(StateDelta { actions })
+    }
+}
+
+/// the deal of a round and every action taken since, enough to replay it
+#[derive(Clone, Debug)]
+pub struct RoundHistory {
+    deal: Round,
+    actions: Vec<Action>,
+}
+
+impl RoundHistory {
+    /// start recording a round that was just dealt
+    pub fn new(deal: Round) -> Self {
+        assert!(deal.phase() == RoundPhase::PickTrump);
+        assert!(deal.played_cards().is_empty());
+
+        RoundHistory {
+            deal,
+            actions: vec![],
+        }
+    }
+
+    /// forget the previous round and start recording `deal`, see [`RoundHistory::new`]
+    pub fn restart(&mut self, deal: Round) {
+        assert!(deal.phase() == RoundPhase::PickTrump);
+        assert!(deal.played_cards().is_empty());
+
+        self.deal = deal;
+        self.actions.clear();
+    }
+
+    pub fn record(&mut self, action: Action) {
+        self.actions.push(action);
+    }
+
+    /// the round right after dealing, before trump is picked
+    pub const fn deal(&self) -> Round {
+        self.deal
+    }
+
+    pub const fn initial_cards(&self, player: usize) -> Stack {
+        self.deal.player_cards(player)
+    }
+
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// the round after all recorded actions
+    pub fn current(&self) -> Round {
+        self.replay(|_, _| {})
+    }
+
+    /// restart this deal from the beginning, playing with `trump`
+    pub fn clone_with_trump(&self, trump: Option<Suit>) -> Round {
+        let mut round = self.deal;
+        round.apply_action(Action::PickTrump(trump));
+
+        round
+    }
+
+    /// the position at the start of trick `trick_index`, replayed from the deal
+    pub fn state_before_trick(&self, trick_index: usize) -> Round {
+        let mut round = self.deal;
+
+        for &action in &self.actions {
+            if round.phase() == RoundPhase::PlayCards && round.tricks_played() == trick_index {
+                break;
+            }
+            round.apply_action(action);
+        }
+
+        assert!(round.phase() == RoundPhase::PlayCards);
+        assert!(
+            round.tricks_played() == trick_index,
+            "trick {trick_index} wasn't played yet"
+        );
+
+        round
+    }
+
+    /// every finished trick of this round, in the order they were played
+    pub fn trick_history(&self) -> Vec<Trick> {
+        let mut tricks = vec![];
+        self.replay(|trick, _| tricks.push(*trick));
+
+        tricks
+    }
+
+    /// a human readable record of the tricks played so far: a header with the dealer
+    /// and contract, and for every trick its cards with the winning card marked by a `*`,
+    /// the points it's worth and the score after it
+    pub fn to_annotated(&self) -> String {
+        let mut tricks = String::new();
+        let round = self.replay(|trick, round| {
+            let (winning_card, winner) = trick.winner().unwrap();
+            let cards = trick.cards().into_vec().into_iter().map(|card| {
+                let mark = if card == winning_card { "*" } else { "" };
+                format!("{card}{mark}")
+            });
+
+            write!(
+                tricks,
+                "\n{}. {}\nplayer {winner} wins {} points, score: {:?}\n",
+                round.tricks_played(),
+                cards.collect::<Vec<_>>().join(" "),
+                trick.score(),
+                round.scores()
+            )
+            .unwrap();
+        });
+
+        let mut text = format!("dealer: {}\n", round.dealer());
+        if round.phase() == RoundPhase::PickTrump {
+            return text;
+        }
+
+        text += &format!("contract: {}\n", round.contract());
+        text + &tricks
+    }
+
+    /// the notation of this round as read by [`crate::io::replay`], with the cards
+    /// played so far. `None` while the contract hasn't been chosen yet
+    pub fn to_notation(&self) -> Option<String> {
+        let round = self.current();
+        let contract = match round.contract() {
+            _ if round.phase() == RoundPhase::PickTrump => return None,
+            Contract::Trump(suit) => suit as u8,
+            Contract::NoTrump => 4,
+            Contract::Solo { .. } => 5,
+            Contract::Misere { .. } => 6,
+        };
+
+        fn to_indices(cards: impl Iterator<Item = Card>) -> String {
+            let indices = cards.map(|card| card.get_index().to_string());
+            indices.collect::<Vec<_>>().join(" ")
+        }
+
+        let played = self.actions.iter().filter_map(|&action| match action {
+            Action::PlayCard(card) => Some(card),
+            _ => None,
+        });
+
+        let mut parts = vec![round.dealer().to_string()];
+        parts.extend((0..4).map(|i| to_indices(self.initial_cards(i).into_iter())));
+        parts.push(contract.to_string());
+        parts.push(to_indices(played));
+
+        Some(parts.join(";"))
+    }
+
+    /// the actions that lead from `prev`, an earlier state of this round,
+    /// to the current one, see [`Round::apply_delta`]
+    pub fn diff(&self, prev: &Round) -> StateDelta {
+        let mut round = self.deal;
+        let mut done = 0;
+
+        while round != *prev {
+            assert!(
+                done < self.actions.len(),
+                "not an earlier state of this round"
+            );
+            round.apply_action(self.actions[done]);
+            done += 1;
+        }
+
+        StateDelta {
+            actions: self.actions[done..].to_vec(),
+        }
+    }
+
+    /// replay every recorded action from the deal, calling `on_trick` with every
+    /// finished trick and the round right after it. Returns the final round
+    fn replay(&self, mut on_trick: impl FnMut(&Trick, &Round)) -> Round {
+        let mut round = self.deal;
+        let mut trick = *round.trick_ref();
+
+        for &action in &self.actions {
+            let Action::PlayCard(card) = action else {
+                round.apply_action(action);
+                continue;
+            };
+
+            if round.cards_in_current_trick() == 0 {
+                trick = *round.trick_ref();
+            }
+            trick.play(card, round.turn());
+            round.apply_action(action);
+
+            if trick.is_finished() {
+                on_trick(&trick, &round);
+            }
+        }
+
+        round
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::{action_list::ActionList, state::State};
+
+    use super::{RoundHistory, StateDelta};
+    use crate::{
+        action::Action,
+        round::{Round, RoundPhase},
+        stack::Stack,
+        suit::Suit,
+    };
+
+    /// play a random action on `round` and record it
+    fn play_random(round: &mut Round, history: &mut RoundHistory) {
+        let action = round.possible_actions().pop_random().unwrap();
+        round.apply_action(action);
+        history.record(action);
+    }
+
+    #[test]
+    fn test_to_annotated() {
+        let mut round = Round::new(1);
+        let mut history = RoundHistory::new(round);
+        assert_eq!(history.to_annotated(), "dealer: 1\n");
+
+        while !round.is_terminal() {
+            play_random(&mut round, &mut history);
+        }
+
+        let text = history.to_annotated();
+        assert!(text.starts_with(&format!("dealer: 1\ncontract: {}\n", round.contract())));
+        assert_eq!(text.split("\n\n").count(), 9);
+        assert_eq!(text.matches('*').count(), 8);
+        assert!(text.trim_end().ends_with(&format!("{:?}", round.scores())));
+    }
+
+    #[test]
+    fn test_trick_history() {
+        let mut round = Round::new(1);
+        let mut history = RoundHistory::new(round);
+        assert_eq!(history.trick_history(), vec![]);
+
+        round.apply_action(Action::PickTrump(Some(Suit::Clubs)));
+        history.record(Action::PickTrump(Some(Suit::Clubs)));
+        let mut leaders = vec![];
+        while !round.is_terminal() {
+            if round.cards_in_current_trick() == 0 {
+                leaders.push(round.turn());
+            }
+            play_random(&mut round, &mut history);
+
+            assert_eq!(history.trick_history().len(), round.tricks_played());
+            assert_eq!(history.current(), round);
+        }
+
+        let tricks = history.trick_history();
+        assert_eq!(tricks.len(), 8);
+
+        let mut played = Stack::default();
+        for (trick, leader) in tricks.iter().zip(leaders) {
+            assert!(trick.is_finished());
+            assert_eq!(trick.leader(), Some(leader));
+            played |= trick.cards().iter().copied().collect::<Stack>();
+        }
+        assert!(played == round.played_cards());
+
+        // the winner of a trick leads the next one
+        for pair in tricks.windows(2) {
+            assert_eq!(pair[0].winner().unwrap().1, pair[1].leader().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_delta() {
+        let mut round = Round::new(2);
+        let mut history = RoundHistory::new(round);
+        round.apply_action(Action::PickTrump(Some(Suit::Spades)));
+        history.record(Action::PickTrump(Some(Suit::Spades)));
+        for _ in 0..6 {
+            play_random(&mut round, &mut history);
+        }
+
+        let prev = round;
+        for _ in 0..7 {
+            play_random(&mut round, &mut history);
+        }
+
+        let delta = history.diff(&prev);
+        let bytes = delta.to_bytes();
+        assert_eq!(bytes.len(), 7);
+        assert_eq!(StateDelta::from_bytes(&bytes), Some(delta));
+
+        let next = prev.apply_delta(&StateDelta::from_bytes(&bytes).unwrap());
+        assert_eq!(next, round);
+    }
+
+    #[test]
+    fn test_delta_trump() {
+        let mut round = Round::new(1);
+        round.set_allow_pass(true);
+        let prev = round;
+        let mut history = RoundHistory::new(round);
+
+        for action in [Action::PassTrump, Action::PickSolo] {
+            round.apply_action(action);
+            history.record(action);
+        }
+        for _ in 0..5 {
+            play_random(&mut round, &mut history);
+        }
+
+        let bytes = history.diff(&prev).to_bytes();
+        assert_eq!(bytes.len(), 7);
+
+        let next = prev.apply_delta(&StateDelta::from_bytes(&bytes).unwrap());
+        assert_eq!(next, round);
+        assert!(history.diff(&round).actions().is_empty());
+
+        assert_eq!(StateDelta::from_bytes(&[3, 40]), None);
+    }
+
+    #[test]
+    fn test_clone_with_trump() {
+        let mut round = Round::new(3);
+        let mut history = RoundHistory::new(round);
+        while round.tricks_played() < 3 {
+            play_random(&mut round, &mut history);
+        }
+
+        let suits: [Suit; 4] = std::array::from_fn(|i| Suit::from(i as u8));
+        let clones = suits.map(|suit| history.clone_with_trump(Some(suit)));
+
+        for (clone, suit) in clones.iter().zip(suits) {
+            assert_eq!(clone.trump(), Some(suit));
+            assert_eq!(clone.turn(), 0);
+            assert_eq!(clone.scores(), [0, 0]);
+            assert!(clone.played_cards().is_empty());
+
+            for i in 0..4 {
+                assert!(clone.player_cards(i) == clones[0].player_cards(i));
+                assert!(clone.player_cards(i) == history.initial_cards(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deal() {
+        let start = Round::new(1);
+        let mut round = start;
+        let mut history = RoundHistory::new(round);
+        while round.tricks_played() < 5 {
+            play_random(&mut round, &mut history);
+        }
+
+        let deal = history.deal();
+        assert!(deal.played_cards().is_empty());
+        assert_eq!(deal.phase(), RoundPhase::PickTrump);
+        assert_eq!(deal, start);
+
+        history.restart(Round::new(2));
+        assert!(history.actions().is_empty());
+        assert_eq!(history.deal().dealer(), 2);
+    }
+
+    #[test]
+    fn test_state_before_trick() {
+        let mut round = Round::new(2);
+        let mut history = RoundHistory::new(round);
+        play_random(&mut round, &mut history);
+
+        let after_deal = round;
+        let mut before_trick = vec![round];
+        while !round.is_terminal() {
+            play_random(&mut round, &mut history);
+            if round.cards_in_current_trick() == 0 {
+                before_trick.push(round);
+            }
+        }
+
+        assert_eq!(history.state_before_trick(0), after_deal);
+        assert!(history.state_before_trick(8).is_terminal());
+        for (i, state) in before_trick.iter().enumerate() {
+            assert_eq!(history.state_before_trick(i), *state);
+        }
+    }
+}
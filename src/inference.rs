@@ -18,10 +18,17 @@ impl Inference {
         self.players[player].l[card.get_index() as usize]
     }
 
+    /// multiply the likelihood of `player` holding `card` by `factor`
+    pub fn scale(&mut self, player: usize, card: Card, factor: f32) {
+        self.players[player].scale(card, factor);
+        self.players[player].rescale();
+    }
+
     pub fn infer(&mut self, state: &Round, action: Action, player: usize) {
         match action {
             Action::PlayCard(card) => self.infer_card(state, player, card),
             Action::PickTrump(trump) => self.infer_trump(state, player, trump),
+            Action::PickSolo | Action::PickMisere | Action::PassTrump => (),
         }
 
         for player in &mut self.players {
@@ -45,7 +52,7 @@ impl Inference {
         }
 
         if let Some((winning_card, winning_player)) = state.trick_ref().winner() {
-            let is_losing_trick = winning_player % 2 != player % 2;
+            let is_losing_trick = state.side_of(winning_player) != state.side_of(player);
 
             // if the player is losing the trick and follows without buying,
             // that means they have no higher cards of that suit
@@ -0,0 +1,125 @@
+use ismcts::state::State;
+
+use crate::{action::Action, card::Card, inference::Inference, round::Round};
+
+/// builds an [`Inference`] from the actions played so far this round. On top of the
+/// constraints of [`Inference::infer`], a player that could overtrump their partner
+/// but discards instead is assumed to probably not hold any higher trumps
+pub struct InferenceModel {
+    /// applied to the likelihood of the trumps above the winning trump
+    /// after a player declined to overtrump
+    pub decline_factor: f32,
+}
+
+impl Default for InferenceModel {
+    fn default() -> Self {
+        InferenceModel {
+            decline_factor: 0.3,
+        }
+    }
+}
+
+impl InferenceModel {
+    /// `history` holds every action of the round, along with the state it was played in
+    pub fn infer(&self, history: &[(Round, Action)]) -> Inference {
+        let mut inference = Inference::default();
+
+        for (state, action) in history {
+            let player = state.turn();
+            inference.infer(state, *action, player);
+
+            if let Action::PlayCard(card) = action {
+                self.infer_decline(&mut inference, state, player, *card);
+            }
+        }
+
+        inference
+    }
+
+    fn infer_decline(&self, inference: &mut Inference, state: &Round, player: usize, card: Card) {
+        let (Some(trump), Some(led), Some((winning_card, winner))) = (
+            state.trump(),
+            state.suit_to_follow(),
+            state.trick_ref().winner(),
+        ) else {
+            return;
+        };
+
+        // when the opponents are winning the player has to overtrump if possible,
+        // which is already covered by the hard constraints
+        let partner_winning = state.side_of(winner) == state.side_of(player);
+        let declined = led != trump
+            && winning_card.suit() == trump
+            && card.suit() != led
+            && card.suit() != trump;
+
+        if partner_winning && declined {
+            for high in state
+                .unplayed_cards()
+                .of_suit(trump)
+                .above(winning_card)
+                .into_iter()
+            {
+                inference.scale(player, high, self.decline_factor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::state::State;
+
+    use super::InferenceModel;
+    use crate::{
+        action::Action,
+        card::Card,
+        inference::Inference,
+        rng,
+        round::{Round, RoundPhase},
+        stack::{Stack, HEARTS},
+        suit::Suit,
+    };
+
+    #[test]
+    fn test_declined_overtrump() {
+        rng::seed_from(1);
+
+        let mut round = Round::from_observer(
+            Stack::from_slice(&[8, 9, 10, 11, 12, 13, 14, 15]),
+            Stack::default(),
+            [8; 4],
+            0,
+            1,
+            RoundPhase::PlayCards,
+            Some(Suit::Hearts),
+            [0; 2],
+        );
+
+        // player 2 ruffs the seven of spades, after which their partner discards a club
+        let mut history = vec![];
+        for card in [0, 16, 1, 8] {
+            let action = Action::PlayCard(Card::new(card));
+            history.push((round, action));
+            round.apply_action(action);
+        }
+
+        let mut plain = Inference::default();
+        for (state, action) in &history {
+            plain.infer(state, *action, state.turn());
+        }
+        let model = InferenceModel::default().infer(&history);
+
+        let high_trumps = Stack::default() | HEARTS & !(1 << 16 | 1 << 17);
+        let count = |inference: &Inference| {
+            (0..1000)
+                .filter(|_| {
+                    let sample = round.randomize(1, inference);
+                    !(sample.player_cards(0) & high_trumps).is_empty()
+                })
+                .count()
+        };
+
+        assert!(count(&model) < count(&plain));
+    }
+}
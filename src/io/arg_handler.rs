@@ -1,11 +1,16 @@
+use crate::game::{Game, Verbosity};
+use crate::players::human_player::HumanPlayer;
 use crate::players::mcts_player::MctsPlayer;
 use crate::players::random_player::RandomPlayer;
 use crate::players::PlayerVec;
 use crate::sprt::run_sprt;
 use crate::tournament::run_tournament_multithreaded;
-use crate::{bench::bench, players::Player};
+use crate::{
+    bench::{bench, bench_rollouts, bench_search},
+    players::Player,
+};
 
-use super::{debugger, input};
+use super::{debugger, input, replay::replay};
 
 pub fn handle_args(args: Vec<String>) {
     if args.contains(&"bench".to_owned()) {
@@ -13,10 +18,49 @@ pub fn handle_args(args: Vec<String>) {
         bench(size);
     }
 
+    if args.contains(&"bench-search".to_owned()) {
+        let playouts = input::read_parsed("playouts per action: ").unwrap_or(20_000);
+        bench_search(playouts);
+    }
+
+    if args.contains(&"bench-rollout".to_owned()) {
+        let games = input::read_parsed("paired games: ").unwrap_or(50);
+        bench_rollouts(games);
+    }
+
     if args.contains(&"d".to_owned()) {
         debugger::run();
     }
 
+    if args.contains(&"interactive".to_owned()) {
+        let think_time = input::read_parsed("think time: ").unwrap_or(1000);
+        let players: PlayerVec = vec![
+            HumanPlayer::boxed(),
+            Box::new(MctsPlayer::new(think_time, true)),
+            Box::new(MctsPlayer::new(think_time, true)),
+            Box::new(MctsPlayer::new(think_time, true)),
+        ];
+
+        let mut game = Game::new(players);
+        game.set_verbosity(Verbosity::Full);
+
+        while !game.is_terminal() {
+            game.play_round();
+        }
+
+        match game.winner() {
+            Some(team) => println!("team {team} wins the game"),
+            None => println!("the game is a draw"),
+        }
+    }
+
+    if args.contains(&"replay".to_owned()) {
+        let notation = input::read_parsed::<String>("notation: ").unwrap();
+        if let Err(err) = replay(&notation) {
+            println!("{err}");
+        }
+    }
+
     if args.contains(&"sprt".to_owned()) {
         let think_time = input::read_parsed("think time: ").unwrap_or(100);
         let player_gen = move || -> PlayerVec {
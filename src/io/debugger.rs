@@ -6,11 +6,12 @@ use crate::inference::Inference;
 use crate::io::input;
 use crate::players::mcts_player::MctsPlayer;
 use crate::players::Player;
+use crate::rng;
 use crate::round::{Round, RoundPhase};
 use crate::stack::Stack;
 
 pub fn run() {
-    let mut state = Round::new(romu::range_usize(0..4));
+    let mut state = Round::new(rng::range_usize(0..4));
     let mut inference = Inference::default();
     let mut player = MctsPlayer::new(1000, true);
     let mut observer = None;
@@ -41,7 +42,7 @@ pub fn run() {
                     dbg!(state.possible_actions());
                 }
                 'n' => {
-                    state = Round::new(romu::range_usize(0..4));
+                    state = Round::new(rng::range_usize(0..4));
                     inference = Inference::default();
                 }
                 'r' => {
@@ -53,7 +54,7 @@ pub fn run() {
                 }
                 'f' => {
                     let actions = match state.phase() {
-                        RoundPhase::PickTrump => ActionCollection::Trumps(0b11111),
+                        RoundPhase::PickTrump => ActionCollection::Trumps(0b11111111),
                         RoundPhase::PlayCards => {
                             ActionCollection::Cards(Stack::ALL ^ state.played_cards())
                         }
@@ -0,0 +1,104 @@
+//! keeps the notation of the most recent games in memory, e.g. to inspect the games
+//! leading up to a crash or a strange decision in a long running process
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+};
+
+use crate::game::Game;
+
+/// a ring buffer with the notation of the last `capacity` games, see [`Game::notation`]
+pub struct GameLog {
+    capacity: usize,
+    games: VecDeque<String>,
+}
+
+impl GameLog {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            capacity,
+            games: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// store a finished game, dropping the oldest one when the log is full.
+    /// The game has to record its notation, see [`Game::set_record_notation`]
+    pub fn record(&mut self, game: &Game) {
+        self.push(game.notation());
+    }
+
+    pub fn push(&mut self, notation: String) {
+        if self.games.len() == self.capacity {
+            self.games.pop_front();
+        }
+
+        self.games.push_back(notation);
+    }
+
+    /// the logged games, oldest first
+    pub fn games(&self) -> impl Iterator<Item = &str> {
+        self.games.iter().map(String::as_str)
+    }
+
+    /// write every logged game, oldest first and separated by an empty line
+    pub fn dump(&self, output: &mut impl Write) -> io::Result<()> {
+        for notation in &self.games {
+            writeln!(output, "{notation}\n")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameLog;
+    use crate::{
+        game::Game,
+        io::replay::replay_to,
+        players::{random_player::RandomPlayer, Player, PlayerVec},
+    };
+
+    #[test]
+    fn test_keeps_last_games() {
+        const CAPACITY: usize = 3;
+
+        let mut log = GameLog::new(CAPACITY);
+        let mut notations = vec![];
+
+        for _ in 0..CAPACITY + 5 {
+            let players: PlayerVec = (0..4).map(|_| RandomPlayer::boxed() as _).collect();
+            let mut game = Game::new(players);
+            game.set_record_notation(true);
+            while !game.is_terminal() {
+                game.play_round();
+            }
+
+            log.record(&game);
+            notations.push(game.notation());
+        }
+
+        assert_eq!(log.len(), CAPACITY);
+        assert!(log.games().eq(notations[5..].iter().map(String::as_str)));
+
+        for round in log.games().flat_map(str::lines) {
+            replay_to(round, &mut vec![]).unwrap();
+        }
+
+        let mut output = vec![];
+        log.dump(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.split("\n\n").count(), CAPACITY + 1);
+    }
+}
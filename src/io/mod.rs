@@ -1,3 +1,7 @@
 pub mod arg_handler;
 mod debugger;
-mod input;
\ No newline at end of file
+pub mod game_log;
+pub(crate) mod input;
+pub mod replay;
+#[cfg(feature = "server")]
+pub mod server;
@@ -0,0 +1,187 @@
+//! replays a finished round from its notation, which looks like
+//! `dealer;hand 0;hand 1;hand 2;hand 3;contract;cards`:
+//! hands and cards are lists of card indices separated by spaces, the cards in the order
+//! they were played. The contract is a suit index, 4 for no trump, 5 for solo and 6 for misère
+
+use std::{fmt::Display, io::Write};
+
+use ismcts::state::State;
+
+use crate::{
+    action::Action, card::Card, game::IllegalMove, round::Round, stack::Stack, suit::Suit,
+};
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Invalid(String),
+    Illegal(IllegalMove),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Invalid(message) => write!(f, "invalid notation: {message}"),
+            ReplayError::Illegal(illegal) => write!(f, "{illegal}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// print every trick of the round in `notation` and the score after it
+pub fn replay(notation: &str) -> Result<(), ReplayError> {
+    replay_to(notation, &mut std::io::stdout())
+}
+
+pub fn replay_to(notation: &str, output: &mut impl Write) -> Result<(), ReplayError> {
+    let (mut round, actions) = parse(notation)?;
+
+    for action in actions {
+        if !round.is_legal(&action) {
+            return Err(ReplayError::Illegal(IllegalMove {
+                seat: round.turn(),
+                action,
+                legal: round.possible_actions(),
+            }));
+        }
+
+        let mut trick = *round.trick_ref();
+        if let Action::PlayCard(card) = action {
+            trick.play(card, round.turn());
+        }
+
+        if let Some((winner, points)) = round.apply_action_observed(action).winner {
+            writeln!(
+                output,
+                "{trick}\tplayer {winner} wins {points} points, score: {:?}",
+                round.scores()
+            )
+            .unwrap();
+        }
+    }
+
+    Ok(())
+}
+
+fn parse(notation: &str) -> Result<(Round, Vec<Action>), ReplayError> {
+    let parts = notation.trim().split(';').collect::<Vec<_>>();
+    let [dealer, hands @ .., contract, cards] = parts.as_slice() else {
+        return Err(ReplayError::Invalid(format!(
+            "expected 7 parts, found {}",
+            parts.len()
+        )));
+    };
+    if hands.len() != 4 {
+        return Err(ReplayError::Invalid(format!(
+            "expected 4 hands, found {}",
+            hands.len()
+        )));
+    }
+
+    let dealer = parse_index(dealer, 4)? as usize;
+
+    let mut player_cards = [Stack::default(); 4];
+    for (i, hand) in hands.iter().enumerate() {
+        player_cards[i] = parse_cards(hand)?.into_iter().collect();
+    }
+    let round = Round::from_parts(dealer, player_cards, Stack::default())
+        .map_err(|err| ReplayError::Invalid(err.to_string()))?;
+
+    let contract = match parse_index(contract, 7)? {
+        index @ 0..=3 => Action::PickTrump(Some(Suit::from(index as u8))),
+        4 => Action::PickTrump(None),
+        5 => Action::PickSolo,
+        _ => Action::PickMisere,
+    };
+
+    let mut actions = vec![contract];
+    actions.extend(parse_cards(cards)?.into_iter().map(Action::PlayCard));
+
+    Ok((round, actions))
+}
+
+fn parse_index(part: &str, max: u32) -> Result<u32, ReplayError> {
+    match part.trim().parse::<u32>() {
+        Ok(index) if index < max => Ok(index),
+        _ => Err(ReplayError::Invalid(format!(
+            "'{part}' is not a number below {max}"
+        ))),
+    }
+}
+
+fn parse_cards(part: &str) -> Result<Vec<Card>, ReplayError> {
+    part.split_whitespace()
+        .map(|index| parse_index(index, 32).map(Card::new))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::{action_list::ActionList, state::State};
+
+    use super::{replay_to, ReplayError};
+    use crate::{action::Action, round::Round};
+
+    fn notation(round: &mut Round) -> String {
+        let hands = (0..4).map(|i| {
+            let cards = round.player_cards(i).into_iter();
+            cards
+                .map(|card| card.get_index().to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+        let mut parts = vec![round.dealer().to_string()];
+        parts.extend(hands);
+
+        round.apply_action(Action::PickTrump(None));
+        parts.push("4".to_owned());
+
+        let mut cards = vec![];
+        while !round.is_terminal() {
+            let action = round.possible_actions().pop_random().unwrap();
+            if let Action::PlayCard(card) = action {
+                cards.push(card.get_index().to_string());
+            }
+            round.apply_action(action);
+        }
+        parts.push(cards.join(" "));
+
+        parts.join(";")
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut round = Round::new(2);
+        let notation = notation(&mut round);
+
+        let mut output = vec![];
+        replay_to(&notation, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 8);
+        assert!(output
+            .lines()
+            .last()
+            .unwrap()
+            .ends_with(&format!("{:?}", round.scores())));
+    }
+
+    #[test]
+    fn test_replay_invalid() {
+        let mut output = vec![];
+
+        let result = replay_to("0;1 2;3;4", &mut output);
+        assert!(matches!(result, Err(ReplayError::Invalid(_))));
+
+        let mut round = Round::new(0);
+        let notation = notation(&mut round);
+        // swap the first two cards that were played
+        let (deal, cards) = notation.rsplit_once(';').unwrap();
+        let mut cards = cards.split(' ').collect::<Vec<_>>();
+        cards.swap(0, 1);
+        let swapped = format!("{deal};{}", cards.join(" "));
+
+        let result = replay_to(&swapped, &mut output);
+        assert!(matches!(result, Err(ReplayError::Illegal(_))));
+    }
+}
@@ -0,0 +1,206 @@
+//! A minimal line based JSON protocol to play a game against a remote client.
+//!
+//! On every turn of the client's seat, the server sends one line with the state
+//! as observed by that seat, e.g.
+//!
+//! ```text
+//! {"seat":0,"turn":0,"dealer":3,"phase":"PlayCards","contract":"♥","scores":[0,0],
+//!  "cards":[1,9,12],"played":[4,5,6,7],"trick":[20],"legal":[{"card":1},{"card":9}]}
+//! ```
+//!
+//! and expects one line with the chosen action back, in the same format as the
+//! entries of `legal`: `{"card":index}`, `{"trump":suit}`, `{"trump":null}`,
+//! `"solo"`, `"misere"` or `"pass"`. After the last round the server sends
+//! `{"winner":team}` (`null` on a draw) and closes the connection.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+};
+
+use ismcts::state::State;
+
+use crate::{
+    action::Action,
+    card::Card,
+    game::Game,
+    inference::Inference,
+    players::{mcts_player::MctsPlayer, Player, PlayerVec},
+    round::Round,
+    suit::Suit,
+};
+
+/// a player whose decisions are made by the client on the other end of `stream`
+pub struct RemotePlayer {
+    seat: usize,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl RemotePlayer {
+    pub fn new(seat: usize, stream: TcpStream) -> io::Result<Self> {
+        Ok(Self {
+            seat,
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    fn exchange(&mut self, round: &Round) -> io::Result<Action> {
+        writeln!(self.writer, "{}", state_to_json(round, self.seat))?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        action_from_json(&line)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, line.trim().to_owned()))
+    }
+}
+
+impl Player for RemotePlayer {
+    fn decide(&mut self, round: Round, _inference: &Inference) -> Action {
+        // the player interface can't fail, so a broken connection ends the game
+        self.exchange(&round)
+            .unwrap_or_else(|err| panic!("client at seat {} failed: {err}", self.seat))
+    }
+}
+
+/// accept a single client on `listener` and play one game with it at `seat`,
+/// the other seats are [`MctsPlayer`]s thinking `think_time` ms per move.
+/// Returns the winning team, `None` on a draw
+pub fn serve(listener: &TcpListener, seat: usize, think_time: u128) -> io::Result<Option<usize>> {
+    let (stream, _) = listener.accept()?;
+    let mut writer = stream.try_clone()?;
+
+    let mut remote = Some(RemotePlayer::new(seat, stream)?);
+    let players: PlayerVec = (0..4)
+        .map(|i| -> Box<dyn Player> {
+            match i == seat {
+                true => Box::new(remote.take().unwrap()),
+                false => Box::new(MctsPlayer::new(think_time, true)),
+            }
+        })
+        .collect();
+
+    let mut game = Game::new(players);
+    while !game.is_terminal() {
+        game.play_round_checked()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    }
+
+    let winner = game.winner();
+    match winner {
+        Some(team) => writeln!(writer, "{{\"winner\":{team}}}")?,
+        None => writeln!(writer, "{{\"winner\":null}}")?,
+    }
+
+    Ok(winner)
+}
+
+/// everything `seat` can see of `round`, as a single line of JSON
+pub fn state_to_json(round: &Round, seat: usize) -> String {
+    let legal = round
+        .possible_actions()
+        .to_vec()
+        .into_iter()
+        .map(action_to_json)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{{\"seat\":{seat},\"turn\":{},\"dealer\":{},\"phase\":\"{:?}\",\"contract\":\"{}\",\
+         \"scores\":[{},{}],\"cards\":{},\"played\":{},\"trick\":{},\"legal\":[{legal}]}}",
+        round.turn(),
+        round.dealer(),
+        round.phase(),
+        round.contract(),
+        round.scores()[0],
+        round.scores()[1],
+        cards_to_json(round.player_cards(seat).into_vec()),
+        cards_to_json(round.played_cards().into_vec()),
+        cards_to_json(round.trick_ref().cards().into_vec()),
+    )
+}
+
+pub fn action_to_json(action: Action) -> String {
+    match action {
+        Action::PlayCard(card) => format!("{{\"card\":{}}}", card.get_index()),
+        Action::PickTrump(Some(suit)) => format!("{{\"trump\":{}}}", suit as u8),
+        Action::PickTrump(None) => "{\"trump\":null}".to_owned(),
+        Action::PickSolo => "\"solo\"".to_owned(),
+        Action::PickMisere => "\"misere\"".to_owned(),
+        Action::PassTrump => "\"pass\"".to_owned(),
+    }
+}
+
+/// the inverse of [`action_to_json`], `None` if `json` isn't a valid action
+pub fn action_from_json(json: &str) -> Option<Action> {
+    let json = json.trim();
+
+    match json {
+        "\"solo\"" => return Some(Action::PickSolo),
+        "\"misere\"" => return Some(Action::PickMisere),
+        "\"pass\"" => return Some(Action::PassTrump),
+        _ => (),
+    }
+
+    let (key, value) = json.strip_prefix('{')?.strip_suffix('}')?.split_once(':')?;
+    let value = value.trim();
+
+    match key.trim() {
+        "\"card\"" => match value.parse::<u32>().ok()? {
+            index @ 0..32 => Some(Action::PlayCard(Card::new(index))),
+            _ => None,
+        },
+        "\"trump\"" if value == "null" => Some(Action::PickTrump(None)),
+        "\"trump\"" => match value.parse::<u8>().ok()? {
+            suit @ 0..4 => Some(Action::PickTrump(Some(Suit::from(suit)))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn cards_to_json(cards: Vec<Card>) -> String {
+    let indices = cards
+        .iter()
+        .map(|card| card.get_index().to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("[{indices}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{action::Action, card::Card};
+
+    use super::{action_from_json, action_to_json};
+
+    #[test]
+    fn test_action_roundtrip() {
+        let actions = [
+            Action::PlayCard(Card::new(0)),
+            Action::PlayCard(Card::new(31)),
+            Action::PickTrump(Some(3.into())),
+            Action::PickTrump(None),
+            Action::PickSolo,
+            Action::PickMisere,
+            Action::PassTrump,
+        ];
+
+        for action in actions {
+            assert_eq!(action_from_json(&action_to_json(action)), Some(action));
+        }
+
+        assert_eq!(
+            action_from_json(" { \"card\" : 12 }\n"),
+            Some(Action::PlayCard(Card::new(12)))
+        );
+        assert_eq!(action_from_json("{\"card\":32}"), None);
+        assert_eq!(action_from_json("{\"trump\":4}"), None);
+        assert_eq!(action_from_json("\"double\""), None);
+    }
+}
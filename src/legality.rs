@@ -0,0 +1,89 @@
+use crate::{round::Round, stack::Stack};
+
+/// further restricts the legal cards of a round, for variants with extra rules,
+/// see [`Round::set_legality_modifier`]
+pub trait LegalityModifier: Sync {
+    /// `cards` are the legal cards under the standard rules,
+    /// should never return an empty stack when `cards` isn't empty
+    fn restrict(&self, round: &Round, cards: Stack) -> Stack;
+}
+
+/// a player that can't follow has to discard the lowest card of one of their suits
+pub struct DiscardLowest;
+
+impl LegalityModifier for DiscardLowest {
+    fn restrict(&self, round: &Round, cards: Stack) -> Stack {
+        let Some(led) = round.suit_to_follow() else {
+            return cards;
+        };
+        if cards.has_suit(led) {
+            return cards;
+        }
+
+        let mut lowest = Stack::default();
+        for card in cards.into_iter() {
+            if cards.lowest_of_suit(card.suit()) == Some(card) {
+                lowest.push(card);
+            }
+        }
+
+        lowest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::state::State;
+
+    use super::{DiscardLowest, LegalityModifier};
+    use crate::{
+        action::Action, action_collection::ActionCollection, card::Card, round::Round, stack::Stack,
+    };
+
+    struct OnlyLowest;
+
+    impl LegalityModifier for OnlyLowest {
+        fn restrict(&self, _round: &Round, cards: Stack) -> Stack {
+            cards.lowest().into_iter().collect()
+        }
+    }
+
+    fn legal_cards(round: &Round) -> Stack {
+        let ActionCollection::Cards(cards) = round.possible_actions() else {
+            unreachable!()
+        };
+        cards
+    }
+
+    #[test]
+    fn test_single_card() {
+        let mut round = Round::new(0);
+        round.apply_action(Action::PickTrump(None));
+        let cards = legal_cards(&round);
+
+        round.set_legality_modifier(Some(&OnlyLowest));
+        assert!(legal_cards(&round) == Stack::from_slice(&[cards.lowest().unwrap().get_index()]));
+
+        round.set_legality_modifier(None);
+        assert!(legal_cards(&round) == cards);
+    }
+
+    #[test]
+    fn test_discard_lowest() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[9, 10, 17, 18, 19, 20, 30, 31]),
+            Stack::from_slice(&[8, 11, 12, 13, 14, 15, 16, 21]),
+            Stack::from_slice(&[22, 23, 24, 25, 26, 27, 28, 29]),
+        ];
+        let mut round = Round::from_deal(3, hands);
+        round.set_legality_modifier(Some(&DiscardLowest));
+        round.apply_action(Action::PickTrump(None));
+
+        assert!(legal_cards(&round) == hands[0]);
+        round.apply_action(Action::PlayCard(Card::new(0)));
+
+        // no spades, so player 1 has to discard
+        assert!(legal_cards(&round) == Stack::from_slice(&[9, 17, 30]));
+    }
+}
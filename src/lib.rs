@@ -0,0 +1,25 @@
+pub mod action;
+pub mod action_collection;
+pub mod analysis;
+pub mod array;
+pub mod bench;
+pub mod bits;
+pub mod card;
+pub mod contract;
+pub mod eval;
+pub mod game;
+pub mod history;
+pub mod inference;
+pub mod inference_model;
+pub mod io;
+pub mod legality;
+pub mod players;
+pub mod rng;
+pub mod rollout;
+pub mod round;
+pub mod sprt;
+pub mod stack;
+pub mod suit;
+pub mod tournament;
+pub mod trick;
+pub mod ui;
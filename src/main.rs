@@ -1,27 +1,12 @@
-use io::arg_handler::handle_args;
-use ui::app::App;
+use manille_master::{bench::CountingAllocator, io::arg_handler::handle_args, rng, ui::app::App};
 
-mod action;
-mod action_collection;
-mod array;
-mod bench;
-mod bits;
-mod card;
-mod game;
-mod inference;
-mod io;
-mod players;
-mod round;
-mod sprt;
-mod stack;
-mod suit;
-mod tournament;
-mod trick;
-mod ui;
+/// lets `bench-search` report the allocations of each search
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() -> eframe::Result {
     let args: Vec<String> = std::env::args().collect();
-    romu::seed();
+    rng::seed();
 
     if args.len() == 1 {
         let options = eframe::NativeOptions::default();
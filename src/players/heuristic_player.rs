@@ -0,0 +1,83 @@
+use ismcts::state::State;
+
+use crate::{
+    action::Action,
+    action_collection::ActionCollection,
+    analysis::evaluate_trumps,
+    inference::Inference,
+    rollout::{GreedyRollout, RolloutPolicy},
+    round::Round,
+};
+
+use super::Player;
+
+/// picks trump by sampling a few playouts for every option, and plays cards greedily
+pub struct HeuristicPlayer {
+    samples: usize,
+}
+
+impl Player for HeuristicPlayer {
+    fn decide(&mut self, round: Round, _inference: &Inference) -> Action {
+        match round.possible_actions() {
+            ActionCollection::Cards(_) => GreedyRollout.choose(&round),
+            _ => self.pick_trump(&round),
+        }
+    }
+}
+
+impl HeuristicPlayer {
+    pub fn new(samples: usize) -> Self {
+        HeuristicPlayer { samples }
+    }
+
+    fn pick_trump(&self, round: &Round) -> Action {
+        evaluate_trumps(round, self.samples)
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap()
+            .0
+    }
+}
+
+impl Default for HeuristicPlayer {
+    fn default() -> Self {
+        HeuristicPlayer { samples: 50 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HeuristicPlayer;
+    use crate::{
+        action::Action,
+        inference::Inference,
+        players::Player,
+        rng,
+        rollout::GreedyRollout,
+        round::{Round, RoundPhase},
+        stack::Stack,
+        suit::Suit,
+    };
+
+    #[test]
+    fn test_pick_trump() {
+        rng::seed_from(1);
+
+        // the jack, ace and ten of hearts against the four lowest clubs,
+        // the dealer picks trump
+        let mut round = Round::from_observer(
+            Stack::from_slice(&[19, 22, 23, 8, 9, 10, 11, 30]),
+            Stack::default(),
+            [8; 4],
+            0,
+            0,
+            RoundPhase::PickTrump,
+            None,
+            [0; 2],
+        );
+        // random playouts overvalue trump length, as nobody holds on to their high trumps
+        round.set_rollout_policy(Some(&GreedyRollout));
+        let action = HeuristicPlayer::new(400).decide(round, &Inference::default());
+        assert_eq!(action, Action::PickTrump(Some(Suit::Hearts)));
+    }
+}
@@ -0,0 +1,32 @@
+use ismcts::state::State;
+
+use crate::{action::Action, inference::Inference, io::input, round::Round};
+
+use super::Player;
+
+/// asks for every action on stdin
+#[derive(Default)]
+pub struct HumanPlayer;
+
+impl Player for HumanPlayer {
+    fn decide(&mut self, round: Round, _inference: &Inference) -> Action {
+        let actions = round.possible_actions().to_vec();
+
+        println!("your cards: {:?}", round.cards_to_move());
+        if let Some(trump) = round.trump() {
+            println!("trump: {trump}");
+        }
+        println!("on the table: {:?}", round.trick_ref().cards().into_vec());
+
+        for (i, action) in actions.iter().enumerate() {
+            println!("{i}: {action}");
+        }
+
+        loop {
+            match input::read_parsed::<usize>("action: ") {
+                Ok(i) if i < actions.len() => return actions[i],
+                _ => println!("pick a number between 0 and {}", actions.len() - 1),
+            }
+        }
+    }
+}
@@ -1,20 +1,45 @@
 use ismcts::{action_list::ActionList, searcher::Searcher, state::State};
 
 use super::Player;
-use crate::{action::Action, inference::Inference, round::Round};
+use crate::{action::Action, inference::Inference, rollout::RolloutPolicy, round::Round};
 
 pub struct MctsPlayer {
     searcher: Searcher<Round>,
     search_time: u128,
     use_inference: bool,
+    rollout_depth: Option<usize>,
+    rollout_policy: Option<&'static dyn RolloutPolicy>,
+    /// statistics of the root's children after the last search
+    last_stats: Vec<MoveStats>,
+    /// search iterations over all decisions
+    num_simulations: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MoveStats {
+    pub action: Action,
+    pub avg_score: f32,
+    pub num_sims: usize,
+    pub num_avails: usize,
+}
+
+impl MoveStats {
+    /// the exploration constant used by [`Searcher::default`]
+    const C: f32 = std::f32::consts::SQRT_2;
+
+    pub fn uct_score(&self) -> f32 {
+        let n = self.num_sims as f32;
+        self.avg_score + Self::C * ((self.num_avails as f32).ln() / n).sqrt()
+    }
 }
 
 impl Player for MctsPlayer {
-    fn decide(&mut self, round: Round, inference: &Inference) -> Action {
+    fn decide(&mut self, mut round: Round, inference: &Inference) -> Action {
         #[cfg(not(feature = "debug"))]
         {
-            let mut actions = round.possible_actions();
-            if actions.len() == 1 {
+            if round.is_forced() {
+                let mut actions = round.possible_actions();
+                self.last_stats.clear();
                 return actions.pop_random().unwrap();
             }
         }
@@ -24,7 +49,21 @@ impl Player for MctsPlayer {
             &Inference::default()
         };
 
+        round.set_rollout_depth(self.rollout_depth);
+        round.set_rollout_policy(self.rollout_policy);
         let result = self.searcher.search(&round, inference, self.search_time);
+        self.num_simulations += result.num_simulations;
+        self.last_stats = result
+            .child_stats
+            .iter()
+            .map(|&(stats, action)| MoveStats {
+                action,
+                avg_score: stats.avg_score,
+                num_sims: stats.num_sims,
+                num_avails: stats.num_avails,
+            })
+            .collect();
+
         #[cfg(feature = "debug")]
         {
             println!(
@@ -42,6 +81,10 @@ impl Player for MctsPlayer {
         }
         result.best_action.unwrap()
     }
+
+    fn num_simulations(&self) -> usize {
+        self.num_simulations
+    }
 }
 
 impl MctsPlayer {
@@ -50,6 +93,10 @@ impl MctsPlayer {
             searcher: Searcher::default(),
             search_time,
             use_inference,
+            rollout_depth: None,
+            rollout_policy: None,
+            last_stats: vec![],
+            num_simulations: 0,
         }
     }
 
@@ -58,9 +105,36 @@ impl MctsPlayer {
         self
     }
 
+    /// the statistics of `action` in the last search
+    pub fn explain(&self, action: Action) -> Option<String> {
+        let stats = self.last_stats.iter().find(|s| s.action == action)?;
+
+        Some(format!(
+            "{action}: sims={}, avg score={:.5}, uct score={:.5}",
+            stats.num_sims,
+            stats.avg_score,
+            stats.uct_score()
+        ))
+    }
+
+    pub fn last_stats(&self) -> &[MoveStats] {
+        &self.last_stats
+    }
+
     pub const fn get_search_time(&self) -> u128 {
         self.search_time
     }
+
+    /// truncate rollouts after `depth` actions and score them by their card point margin
+    pub fn set_rollout_depth(mut self, depth: Option<usize>) -> Self {
+        self.rollout_depth = depth;
+        self
+    }
+
+    pub fn set_rollout_policy(mut self, policy: &'static dyn RolloutPolicy) -> Self {
+        self.rollout_policy = Some(policy);
+        self
+    }
 }
 
 impl Default for MctsPlayer {
@@ -69,6 +143,31 @@ impl Default for MctsPlayer {
             searcher: Searcher::default(),
             search_time: 500,
             use_inference: true,
+            rollout_depth: None,
+            rollout_policy: None,
+            last_stats: vec![],
+            num_simulations: 0,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MctsPlayer;
+    use crate::{action::Action, inference::Inference, players::Player, round::Round};
+
+    #[test]
+    fn test_explain() {
+        let mut player = MctsPlayer::new(20, false);
+        let round = Round::new(0);
+
+        let action = player.decide(round, &Inference::default());
+        let explanation = player.explain(action).unwrap();
+
+        assert!(explanation.starts_with(&action.to_string()));
+        assert!(player
+            .explain(Action::PlayCard(Default::default()))
+            .is_none());
+        assert!(player.num_simulations() > 0);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{action::Action, inference::Inference, round::Round};
 
+pub mod heuristic_player;
+pub mod human_player;
 pub mod mcts_player;
+pub mod observed_player;
 pub mod random_player;
 
 pub type PlayerVec = Vec<Box<dyn Player>>;
@@ -14,4 +17,10 @@ pub trait Player {
     }
 
     fn decide(&mut self, round: Round, inference: &Inference) -> Action;
+
+    /// the total number of search iterations over all decisions so far,
+    /// 0 for players that don't search
+    fn num_simulations(&self) -> usize {
+        0
+    }
 }
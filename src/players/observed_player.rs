@@ -0,0 +1,72 @@
+use ismcts::state::State;
+
+use crate::{action::Action, inference::Inference, round::Round};
+
+use super::Player;
+
+/// only lets the wrapped player see what the player to move could know,
+/// see [`Round::observed_by`]
+#[derive(Default)]
+pub struct ObservedPlayer<P: Player> {
+    player: P,
+}
+
+impl<P: Player> Player for ObservedPlayer<P> {
+    fn decide(&mut self, round: Round, inference: &Inference) -> Action {
+        let observed = round.observed_by(round.turn());
+        self.player.decide(observed, inference)
+    }
+}
+
+impl<P: Player> ObservedPlayer<P> {
+    pub fn new(player: P) -> Self {
+        ObservedPlayer { player }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::state::State;
+
+    use super::ObservedPlayer;
+    use crate::{
+        action::Action,
+        inference::Inference,
+        players::{heuristic_player::HeuristicPlayer, random_player::RandomPlayer, Player},
+        round::Round,
+    };
+
+    /// a fair player makes the same decisions whether or not it can see the other hands
+    fn assert_fair_play(mut full: impl Player, observed: impl Player) {
+        let mut observed = ObservedPlayer::new(observed);
+
+        for dealer in 0..4 {
+            let mut round = Round::new(dealer);
+            round.apply_action(Action::PickTrump(None));
+
+            while !round.is_terminal() {
+                let action = full.decide(round, &Inference::default());
+                assert_eq!(observed.decide(round, &Inference::default()), action);
+
+                round.apply_action(action);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fair_play() {
+        assert_fair_play(RandomPlayer::with_seed(5), RandomPlayer::with_seed(5));
+        assert_fair_play(HeuristicPlayer::default(), HeuristicPlayer::default());
+    }
+
+    #[test]
+    fn test_observed_by() {
+        let round = Round::new(1);
+        let observed = round.observed_by(2);
+
+        assert!(observed.player_cards(2) == round.player_cards(2));
+        for i in 0..4 {
+            assert_eq!(observed.player_cards(i).len(), round.player_cards(i).len());
+        }
+    }
+}
@@ -1,14 +1,127 @@
 use ismcts::{action_list::ActionList, state::State};
 
-use crate::{action::Action, inference::Inference, round::Round};
+use crate::{
+    action::Action,
+    action_collection::ActionCollection,
+    inference::Inference,
+    rng::SeededRng,
+    round::Round,
+    stack::{Stack, SEVENS},
+};
 
 use super::Player;
 
 #[derive(Default)]
-pub struct RandomPlayer;
+pub struct RandomPlayer {
+    /// don't waste high cards when our team is already winning the trick
+    partner_aware: bool,
+    /// `None` uses the thread-local generator
+    rng: Option<SeededRng>,
+}
 
 impl Player for RandomPlayer {
     fn decide(&mut self, round: Round, _inference: &Inference) -> Action {
-        round.possible_actions().pop_random().unwrap()
+        let mut actions = round.possible_actions();
+
+        if self.partner_aware {
+            if let Some(cards) = Self::low_cards(&round, actions) {
+                actions = ActionCollection::Cards(cards);
+            }
+        }
+
+        match &mut self.rng {
+            Some(rng) => {
+                let actions = actions.to_vec();
+                actions[rng.mod_usize(actions.len())]
+            }
+            None => actions.pop_random().unwrap(),
+        }
+    }
+}
+
+impl RandomPlayer {
+    pub fn new(partner_aware: bool) -> Self {
+        RandomPlayer {
+            partner_aware,
+            rng: None,
+        }
+    }
+
+    /// a player whose choices only depend on `seed` and the states it's given
+    pub fn with_seed(seed: u64) -> Self {
+        RandomPlayer {
+            partner_aware: false,
+            rng: Some(SeededRng::new(seed)),
+        }
+    }
+
+    /// the lowest legal cards, if our team is currently winning the trick
+    fn low_cards(round: &Round, actions: ActionCollection) -> Option<Stack> {
+        let ActionCollection::Cards(cards) = actions else {
+            return None;
+        };
+        if !round.partner_winning() {
+            return None;
+        }
+
+        let lowest = cards.lowest()?;
+        Some(cards & SEVENS << lowest.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::{action_list::ActionList, state::State};
+
+    use super::RandomPlayer;
+    use crate::{
+        action::Action,
+        card::Card,
+        inference::Inference,
+        players::Player,
+        round::{Round, RoundPhase},
+        stack::Stack,
+        suit::Suit,
+    };
+
+    #[test]
+    fn test_partner_aware() {
+        let mut round = Round::from_observer(
+            Stack::from_slice(&[1, 6, 8, 16, 17, 24, 25, 26]),
+            Stack::default(),
+            [8; 4],
+            1,
+            2,
+            RoundPhase::PlayCards,
+            Some(Suit::Hearts),
+            [0; 2],
+        );
+
+        // partner leads the king of spades
+        round.apply_action(Action::PlayCard(Card::new(5)));
+        round.apply_action(Action::PlayCard(Card::new(0)));
+
+        let mut player = RandomPlayer::new(true);
+        for _ in 0..20 {
+            let action = player.decide(round, &Inference::default());
+            assert_eq!(action, Action::PlayCard(Card::new(1)));
+        }
+    }
+
+    #[test]
+    fn test_with_seed() {
+        let mut a = RandomPlayer::with_seed(11);
+        let mut b = RandomPlayer::with_seed(11);
+        let mut round = Round::new(0);
+
+        while !round.is_terminal() {
+            let action = a.decide(round, &Inference::default());
+
+            // draws from the thread-local generator don't affect seeded players
+            round.possible_actions().pop_random();
+            assert_eq!(b.decide(round, &Inference::default()), action);
+
+            round.apply_action(action);
+        }
     }
 }
@@ -0,0 +1,132 @@
+//! every bit of randomness (dealing, determinizations, rollouts, random players)
+//! goes through the thread-local romu generator, so seeding it makes
+//! everything that happens on a thread reproducible
+
+use std::ops::Range;
+
+/// seed the generator of the current thread from the system
+pub fn seed() {
+    romu::seed();
+}
+
+/// seed the generator of the current thread
+pub fn seed_from(seed: u64) {
+    romu::seed_with_64bit(seed);
+}
+
+const GAMMA: u64 = 0x9e3779b97f4a7c15;
+
+/// the output function of splitmix64
+const fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// derive the seed of the `index`-th independent stream of `seed`,
+/// e.g. to give each worker thread its own reproducible stream
+pub const fn split(seed: u64, index: u64) -> u64 {
+    mix(seed.wrapping_add((index + 1).wrapping_mul(GAMMA)))
+}
+
+/// a source of random numbers, to pass an explicit generator
+/// where the thread-local one would be used otherwise
+pub trait RngLike {
+    fn mod_usize(&mut self, n: usize) -> usize;
+}
+
+/// a splitmix64 generator with its own state, for when a single component
+/// has to be reproducible regardless of what else uses the thread-local one
+#[derive(Clone, Copy, Debug)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub const fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GAMMA);
+        mix(self.state)
+    }
+
+    pub const fn mod_usize(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+impl RngLike for SeededRng {
+    fn mod_usize(&mut self, n: usize) -> usize {
+        SeededRng::mod_usize(self, n)
+    }
+}
+
+impl RngLike for ThreadRng {
+    fn mod_usize(&mut self, n: usize) -> usize {
+        mod_usize(n)
+    }
+}
+
+pub fn u64() -> u64 {
+    romu::u64()
+}
+
+pub fn mod_u32(n: u32) -> u32 {
+    romu::mod_u32(n)
+}
+
+pub fn mod_usize(n: usize) -> usize {
+    romu::mod_usize(n)
+}
+
+pub fn range_usize(range: Range<usize>) -> usize {
+    romu::range_usize(range)
+}
+
+/// adapter to use the thread-local generator with the `rand` crate
+pub struct ThreadRng;
+
+impl rand::RngCore for ThreadRng {
+    fn next_u32(&mut self) -> u32 {
+        romu::u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        romu::u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        romu::fill_bytes(dst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{seed_from, split, SeededRng};
+
+    #[test]
+    fn test_split() {
+        let seeds = [split(42, 0), split(42, 1), split(43, 0)];
+        assert_ne!(seeds[0], seeds[1]);
+        assert_ne!(seeds[0], seeds[2]);
+        assert_eq!(split(42, 0), seeds[0]);
+
+        seed_from(seeds[0]);
+        let a = romu::u64();
+        seed_from(seeds[0]);
+        assert_eq!(romu::u64(), a);
+    }
+
+    #[test]
+    fn test_seeded_rng() {
+        let mut a = SeededRng::new(3);
+        let mut b = SeededRng::new(3);
+
+        romu::u64();
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_eq!(a.mod_usize(10), b.mod_usize(10));
+        assert_ne!(a.next_u64(), SeededRng::new(4).next_u64());
+    }
+}
@@ -0,0 +1,152 @@
+use ismcts::{action_list::ActionList, state::State};
+
+use crate::{
+    action::Action, action_collection::ActionCollection, card::Card, contract::Contract,
+    round::Round, stack::Stack,
+};
+
+/// picks the actions during rollouts, see [`Round::set_rollout_policy`]
+pub trait RolloutPolicy: Sync {
+    /// should always return a legal action
+    fn choose(&self, round: &Round) -> Action;
+}
+
+/// uniformly random legal actions, the default
+pub struct RandomRollout;
+
+impl RolloutPolicy for RandomRollout {
+    fn choose(&self, round: &Round) -> Action {
+        round.possible_actions().pop_random().unwrap()
+    }
+}
+
+/// plays the cheapest card that wins the trick when the opponents are winning,
+/// or else the lowest card. In a misère the declarer ducks instead, see
+/// [`GreedyRollout::pick_misere_card`]. Trump is still picked randomly
+pub struct GreedyRollout;
+
+impl RolloutPolicy for GreedyRollout {
+    fn choose(&self, round: &Round) -> Action {
+        match round.possible_actions() {
+            ActionCollection::Cards(_) => Action::PlayCard(Self::pick_card(round)),
+            mut actions => actions.pop_random().unwrap(),
+        }
+    }
+}
+
+impl GreedyRollout {
+    pub fn pick_card(round: &Round) -> Card {
+        let ActionCollection::Cards(cards) = round.possible_actions() else {
+            unreachable!()
+        };
+        if let Contract::Misere { declarer } = round.contract() {
+            return Self::pick_misere_card(round, cards, declarer);
+        }
+
+        let lowest = cards.lowest().unwrap();
+        let Some((winning_card, _)) = round.trick_ref().winner() else {
+            return lowest;
+        };
+        if round.partner_winning() {
+            return lowest;
+        }
+
+        let led = round.suit_to_follow().unwrap();
+        cards
+            .into_iter()
+            .filter(|card| card.beats(&winning_card, led, round.trump()))
+            .min_by_key(|card| card.value())
+            .unwrap_or(lowest)
+    }
+
+    /// the declarer of a misère plays the highest card that doesn't take the trick, and
+    /// gets rid of their highest card when they can't avoid taking it. The defenders
+    /// play their lowest card, to leave as many tricks as possible to the declarer
+    fn pick_misere_card(round: &Round, cards: Stack, declarer: usize) -> Card {
+        let lowest = cards.lowest().unwrap();
+        if round.turn() != declarer {
+            return lowest;
+        }
+
+        let Some((winning_card, _)) = round.trick_ref().winner() else {
+            return lowest;
+        };
+
+        let led = round.suit_to_follow().unwrap();
+        cards
+            .into_iter()
+            .filter(|card| !card.beats(&winning_card, led, round.trump()))
+            .max_by_key(|card| card.value())
+            .or_else(|| cards.into_iter().max_by_key(|card| card.value()))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::state::State;
+
+    use super::{GreedyRollout, RolloutPolicy};
+    use crate::{
+        action::Action,
+        card::Card,
+        round::{Round, RoundPhase},
+        stack::Stack,
+        suit::Suit,
+    };
+
+    #[test]
+    fn test_greedy_is_legal() {
+        for dealer in (0..4).cycle().take(200) {
+            let mut round = Round::new(dealer);
+
+            while !round.is_terminal() {
+                let action = GreedyRollout.choose(&round);
+                assert!(round.is_legal(&action));
+                round.apply_action(action);
+            }
+        }
+    }
+
+    #[test]
+    fn test_greedy_buys_cheaply() {
+        let mut round = Round::from_observer(
+            Stack::from_slice(&[1, 4, 6, 7, 16]),
+            Stack::default(),
+            [5, 9, 9, 9],
+            2,
+            3,
+            RoundPhase::PlayCards,
+            Some(Suit::Hearts),
+            [0; 2],
+        );
+
+        round.apply_action(Action::PlayCard(Card::new(3)));
+        // the queen is the cheapest card that beats the jack
+        assert_eq!(GreedyRollout.choose(&round), Action::PlayCard(Card::new(4)));
+    }
+
+    #[test]
+    fn test_greedy_misere_ducks() {
+        let hands = [
+            Stack::from_slice(&[4, 8, 9]),
+            Stack::from_slice(&[0, 10, 11]),
+            Stack::from_slice(&[1, 12, 13]),
+            Stack::from_slice(&[2, 3, 14]),
+        ];
+        let undealt = !hands.iter().fold(Stack::default(), |a, &b| a | b);
+        let mut round = Round::from_parts(3, hands, undealt).unwrap();
+        round.apply_action(Action::PickMisere);
+        assert_eq!(round.contract().declarer(), Some(3));
+
+        // the defenders leave the queen of spades winning
+        round.apply_action(Action::PlayCard(Card::new(4)));
+        for _ in 0..2 {
+            assert_eq!(round.trick_ref().winner().unwrap().1, 0);
+            round.apply_action(GreedyRollout.choose(&round));
+        }
+
+        // so the declarer ducks with the jack, keeping the nine for later
+        assert_eq!(GreedyRollout.choose(&round), Action::PlayCard(Card::new(3)));
+    }
+}
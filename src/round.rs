@@ -1,13 +1,55 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
-use ismcts::state::State;
+use ismcts::{action_list::ActionList, searcher::Searcher, state::State};
 use rand::seq::IndexedRandom;
 
 use crate::{
-    action::Action, action_collection::ActionCollection, card::Card, inference::Inference,
-    stack::Stack, suit::Suit, trick::Trick,
+    action::Action,
+    action_collection::ActionCollection,
+    card::Card,
+    contract::Contract,
+    history::StateDelta,
+    inference::Inference,
+    legality::LegalityModifier,
+    rng::{self, RngLike},
+    rollout::RolloutPolicy,
+    stack::{self, Stack},
+    suit::Suit,
+    trick::Trick,
 };
 
+/// hands that don't make up a valid deal, see [`Round::from_parts`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidDeal {
+    /// the card is in more than one hand, or both in a hand and undealt
+    Duplicate(Card),
+    /// the cards that are neither in a hand nor undealt
+    Missing(Stack),
+    /// not every player holds the same number of cards
+    UnequalHands,
+}
+
+impl Display for InvalidDeal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidDeal::Duplicate(card) => write!(f, "{card} is dealt twice"),
+            InvalidDeal::Missing(cards) => write!(f, "{cards:?} are missing"),
+            InvalidDeal::UnequalHands => write!(f, "not every player has the same number of cards"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidDeal {}
+
+/// what happened when applying an action, see [`Round::apply_action_observed`]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApplyOutcome {
+    pub trick_finished: bool,
+    pub round_phase_changed: bool,
+    /// the player that won the trick and the card points in it
+    pub winner: Option<(usize, u8)>,
+}
+
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RoundPhase {
     #[default]
@@ -21,9 +63,54 @@ pub struct Round {
     dealer: usize,
     player_cards: [Stack; 4],
     played_cards: Stack,
+    /// the cards that aren't used this round, when dealing less than 8 cards each
+    undealt: Stack,
+    scores: [i16; 2],
+    /// number of tricks won by each player
+    tricks: [u8; 4],
+    /// the suits each player is known to be out of, bit i is set for suit i
+    voids: [u8; 4],
+    trick: Trick,
+    contract: Contract,
+    phase: RoundPhase,
+    /// whether the player picking trump may pass it on to the next player
+    allow_pass: bool,
+    /// number of players that passed picking trump this round
+    passes: u8,
+    /// the card points the team that picked trump needs to score more than to win,
+    /// a simple majority is always needed. See [`Round::set_contract_threshold`]
+    contract_threshold: i16,
+    /// stop rollouts after this many actions, see [`Round::heuristic_reward`]
+    rollout_depth: Option<usize>,
+    /// `None` plays uniformly random rollouts
+    rollout_policy: Option<&'static dyn RolloutPolicy>,
+    /// extra rules on top of the standard ones, see [`LegalityModifier`]
+    legality_modifier: Option<&'static dyn LegalityModifier>,
+}
+
+/// the fields of a [`Round`] that change while playing it,
+/// see [`Round::snapshot`] and [`Round::restore`]
+#[derive(Clone, Copy, Debug)]
+pub struct Snapshot {
+    turn: usize,
+    player_cards: [Stack; 4],
+    played_cards: Stack,
     scores: [i16; 2],
+    tricks: [u8; 4],
+    voids: [u8; 4],
     trick: Trick,
+    contract: Contract,
     phase: RoundPhase,
+    passes: u8,
+}
+
+/// the team `seat` belongs to, ignoring the contract, see [`Round::side_of`]
+pub const fn team_of(seat: usize) -> usize {
+    seat % 2
+}
+
+pub const fn partner_of(seat: usize) -> usize {
+    (seat + 2) % 4
 }
 
 impl Round {
@@ -52,15 +139,28 @@ impl Round {
         round.dealer = dealer;
         round.phase = phase;
         round.scores = scores;
+        round.contract = Contract::from(trump);
         round.trick.set_trump(trump);
 
         round
     }
 
+    /// the round as `observer` sees it: the hands of the other players are
+    /// replaced by a random deal of the cards they could still hold
+    pub fn observed_by(&self, observer: usize) -> Self {
+        self.randomize(observer, &Inference::default())
+    }
+
+    /// `n` independent determinizations of this round from the perspective of `observer`,
+    /// see [`Round::observed_by`]
+    pub fn sample_worlds(&self, observer: usize, n: usize) -> Vec<Self> {
+        (0..n).map(|_| self.observed_by(observer)).collect()
+    }
+
     pub fn observe_action(&self, observer: usize, action: Action, inference: &Inference) -> Self {
         let mut round = *self;
         if let Action::PlayCard(card) = action {
-            if !round.player_cards(round.turn()).has_card(card) {
+            if !round.cards_to_move().has_card(card) {
                 round.player_cards[round.turn()].pop_random_card();
             }
         }
@@ -78,40 +178,187 @@ impl Round {
         inference: &Inference,
     ) -> Self {
         let mut round = *self;
-        let mut cards_to_deal = Stack::ALL ^ observer_cards ^ played_cards;
+        let cards_to_deal = Stack::ALL ^ observer_cards ^ played_cards ^ self.undealt;
+        // the observer keeps their own cards
+        player_card_counts[observer] = 0;
+
+        // dealing greedily nearly always works, but can end up with a card that only
+        // fits with players void of its suit, then deal again checking every card
+        let deal = |exact| {
+            self.deal_hidden(
+                observer,
+                cards_to_deal,
+                player_card_counts,
+                inference,
+                exact,
+            )
+        };
+        round.player_cards = deal(false).unwrap_or_else(|| deal(true).unwrap());
+        round.player_cards[observer] = observer_cards;
+
+        round
+    }
+
+    /// deal `cards_to_deal` randomly over the players other than `observer`, `counts[i]`
+    /// cards to player `i`, keeping to the known voids. Without `exact`, every card goes
+    /// to any player that isn't void of its suit, and this returns `None` when there's
+    /// no such player left. With `exact`, a card only goes to a player after which the
+    /// remaining cards can still be dealt, see [`Round::can_deal`]
+    fn deal_hidden(
+        &self,
+        observer: usize,
+        mut cards_to_deal: Stack,
+        mut counts: [usize; 4],
+        inference: &Inference,
+        exact: bool,
+    ) -> Option<[Stack; 4]> {
+        let mut hands = [Stack::default(); 4];
         let mut players = Vec::with_capacity(4);
 
-        for i in 0..4 {
-            round.player_cards[i].clear();
-        }
+        // the suits that players are void of go first, while there's most room left
+        // for them with the other players
+        let mut suits: [Suit; 4] = std::array::from_fn(|i| Suit::from(i as u8));
+        suits.sort_by_key(|&suit| {
+            let voids = (0..4).filter(|&i| i != observer && self.is_void(i, suit));
+            std::cmp::Reverse(voids.count())
+        });
+
+        let mut rng = rng::ThreadRng;
+        for suit in suits {
+            let mut cards = cards_to_deal.of_suit(suit);
+            cards_to_deal &= !suit.mask();
+
+            while let Some(card) = cards.pop_lowest() {
+                for i in 1..=3 {
+                    let player = (observer + i) % 4;
+                    if counts[player] > 0 {
+                        players.push(player);
+                    }
+                }
 
-        let mut rng = rand::rng();
-        while let Some(card) = cards_to_deal.pop_lowest() {
-            for i in 1..=3 {
-                let player = (observer + i) % 4;
-                if player_card_counts[player] > 0 {
-                    players.push(player);
+                let rest = cards | cards_to_deal;
+                let fits = |player: usize| {
+                    let mut counts = counts;
+                    counts[player] -= 1;
+                    !self.is_void(player, suit) && (!exact || self.can_deal(rest, counts))
+                };
+                if players.iter().any(|&player| fits(player)) {
+                    players.retain(|&player| fits(player));
+                } else if !exact {
+                    return None;
                 }
-            }
-            let chosen_player = players
-                .choose_weighted(&mut rng, |&player| inference.weight(player, card))
-                .unwrap_or(players.choose(&mut rng).unwrap());
 
-            round.player_cards[*chosen_player].push(card);
-            player_card_counts[*chosen_player] -= 1;
-            players.clear();
+                let chosen_player = players
+                    .choose_weighted(&mut rng, |&player| inference.weight(player, card))
+                    .unwrap_or(players.choose(&mut rng).unwrap());
+
+                hands[*chosen_player].push(card);
+                counts[*chosen_player] -= 1;
+                players.clear();
+            }
         }
 
-        round.player_cards[observer] = observer_cards;
+        Some(hands)
+    }
+
+    /// whether `cards` can be dealt to players with room for `counts` more cards each,
+    /// without giving anyone a card of a suit they're void of. By Hall's theorem, that's
+    /// the case when for every set of players, the cards that only they could take fit
+    /// in their hands
+    fn can_deal(&self, cards: Stack, counts: [usize; 4]) -> bool {
+        // the players that can still take a card of each suit, bit i is set for player i
+        let takers: [u8; 4] = std::array::from_fn(|suit| {
+            (0..4)
+                .filter(|&i| counts[i] > 0 && !self.is_void(i, Suit::from(suit as u8)))
+                .fold(0, |takers, i| takers | 1 << i)
+        });
+
+        (1..16).all(|set: u8| {
+            let room: usize = (0..4)
+                .filter(|&i| set >> i & 1 != 0)
+                .map(|i| counts[i])
+                .sum();
+            let only_in_set: u32 = (0..4)
+                .filter(|&suit| takers[suit] & !set == 0)
+                .map(|suit| cards.of_suit(Suit::from(suit as u8)).len())
+                .sum();
+
+            only_in_set as usize <= room
+        })
+    }
+
+    /// start a round with the given hands instead of dealing them randomly
+    pub fn from_deal(dealer: usize, player_cards: [Stack; 4]) -> Self {
+        let mut round = Round::default();
+
+        round.set_dealer(dealer);
+        round.player_cards = player_cards;
+        round.undealt = !player_cards
+            .into_iter()
+            .fold(Stack::default(), |a, b| a | b);
 
         round
     }
 
+    /// like [`Round::from_deal`], but checks that the hands and `undealt` contain
+    /// every card exactly once. The undealt cards are left out of the round,
+    /// so only the tricks for the cards in hand are played
+    pub fn from_parts(
+        dealer: usize,
+        player_cards: [Stack; 4],
+        undealt: Stack,
+    ) -> Result<Self, InvalidDeal> {
+        let mut seen = undealt;
+        for cards in player_cards {
+            if let Some(card) = (seen & cards).into_iter().next() {
+                return Err(InvalidDeal::Duplicate(card));
+            }
+            seen |= cards;
+        }
+
+        if seen != Stack::ALL {
+            return Err(InvalidDeal::Missing(!seen));
+        }
+        if player_cards
+            .iter()
+            .any(|cards| cards.len() != player_cards[0].len())
+        {
+            return Err(InvalidDeal::UnequalHands);
+        }
+
+        Ok(Self::from_deal(dealer, player_cards))
+    }
+
     pub fn new(dealer: usize) -> Self {
+        Self::new_with_hand_size(dealer, 8)
+    }
+
+    /// deal exactly `hand` to `seat`, and the other 24 cards randomly to the other players
+    pub fn new_with_hand(dealer: usize, seat: usize, hand: Stack) -> Self {
+        assert_eq!(hand.len(), 8);
+
+        let mut rest = (Stack::ALL & !hand).into_vec();
+        for i in (1..rest.len()).rev() {
+            rest.swap(i, rng::mod_usize(i + 1));
+        }
+
+        let mut player_cards = [hand; 4];
+        let others = (0..4).filter(|&i| i != seat);
+        for (player, cards) in others.zip(rest.chunks(8)) {
+            player_cards[player] = cards.iter().copied().collect();
+        }
+
+        Self::from_deal(dealer, player_cards)
+    }
+
+    /// deal only `hand_size` cards to each player, playing that many tricks
+    pub fn new_with_hand_size(dealer: usize, hand_size: usize) -> Self {
+        assert!((1..=8).contains(&hand_size));
+
         let mut round = Round::default();
 
         round.set_dealer(dealer);
-        round.deal_cards();
+        round.deal_cards(hand_size);
 
         round
     }
@@ -124,35 +371,72 @@ impl Round {
     pub fn setup_for_next_round(&mut self) {
         let next_dealer = (self.dealer + 1) % 4;
         self.set_dealer(next_dealer);
-        self.deal_cards();
+        self.deal_cards(self.num_tricks());
 
         self.played_cards = Stack::default();
         self.scores = [0; 2];
+        self.tricks = [0; 4];
+        self.voids = [0; 4];
         self.trick.clear();
+        self.trick.set_trump(None);
+        self.contract = Contract::default();
         self.phase = RoundPhase::PickTrump;
+        self.passes = 0;
     }
 
-    fn deal_cards(&mut self) {
+    /// deal `n` cards to every player
+    fn deal_cards(&mut self, n: usize) {
         let mut indices: [u32; 32] = std::array::from_fn(|i| i as u32);
-        let mut cards = [Stack::default(); 3];
+        let mut cards = [Stack::default(); 4];
 
-        // number of cards per player
-        let n = indices.len() / 4;
+        // the dealt cards end up in the last 4 * n indices
+        let start = indices.len() - 4 * n;
+        // with a full deck, the last player simply gets the cards that are left
+        let lowest = if start == 0 { n } else { start };
 
-        for i in (n..4 * n).rev() {
-            let j = romu::mod_usize(i + 1);
+        for i in (lowest..indices.len()).rev() {
+            let j = rng::mod_usize(i + 1);
             indices.swap(i, j);
 
-            cards[(i / n) - 1] |= 1 << indices[i];
+            let player = (i - start) / n;
+            if player > 0 {
+                cards[player - 1] |= 1 << indices[i];
+            }
+        }
+
+        for &index in &indices[start..start + n] {
+            cards[3] |= 1 << index;
         }
 
-        self.player_cards[0] = cards[0];
-        self.player_cards[1] = cards[1];
-        self.player_cards[2] = cards[2];
-        self.player_cards[3] = Stack::ALL ^ cards[0] ^ cards[1] ^ cards[2];
+        self.player_cards = cards;
+        self.undealt = Stack::ALL ^ cards[0] ^ cards[1] ^ cards[2] ^ cards[3];
+    }
+
+    /// the number of tricks in this round, i.e. the number of cards dealt to each player
+    pub const fn num_tricks(&self) -> usize {
+        (Stack::ALL.len() - self.undealt.len()) as usize / 4
+    }
+
+    /// the card points of all cards in this round, 60 when dealing the full deck
+    pub fn points_in_play(&self) -> i16 {
+        60 - self
+            .undealt
+            .into_iter()
+            .map(|card| card.score())
+            .sum::<i16>()
+    }
+
+    pub const fn undealt_cards(&self) -> Stack {
+        self.undealt
     }
 
     fn play_card(&mut self, card: Card) {
+        if let Some(suit) = self.trick.suit_to_follow() {
+            if card.suit() != suit {
+                self.voids[self.turn] |= 1 << suit as u8;
+            }
+        }
+
         self.trick.play(card, self.turn);
         self.played_cards |= 1 << card.get_index();
         self.player_cards[self.turn] &= !(1 << card.get_index());
@@ -164,20 +448,180 @@ impl Round {
         }
     }
 
-    const fn set_trump(&mut self, trump: Option<Suit>) {
-        self.trick.set_trump(trump);
+    const fn set_contract(&mut self, contract: Contract) {
+        self.trick.set_trump(contract.trump());
+        self.contract = contract;
         self.phase = RoundPhase::PlayCards;
     }
 
     const fn on_trick_finish(&mut self) {
         let (_, winner) = self.trick.winner().unwrap();
-        let winning_team = winner % 2;
+        let winning_team = self.side_of(winner);
 
         self.scores[winning_team] += self.trick.score();
+        self.tricks[winner] += 1;
         self.turn = winner;
         self.trick.clear();
     }
 
+    /// the team `seat` plays for under the current contract,
+    /// when playing solo or misère the declarer's partner joins the opponents
+    pub const fn side_of(&self, seat: usize) -> usize {
+        match self.contract.declarer() {
+            Some(declarer) if seat != declarer => 1 - team_of(declarer),
+            _ => team_of(seat),
+        }
+    }
+
+    /// whether the declarer managed to take every trick in a solo,
+    /// or none at all in a misère
+    const fn declarer_won(&self, declarer: usize) -> bool {
+        match self.contract {
+            Contract::Misere { .. } => self.tricks[declarer] == 0,
+            _ => self.tricks[declarer] as usize == self.num_tricks(),
+        }
+    }
+
+    /// let players pass picking trump to the next player, the last player can't pass
+    pub const fn set_allow_pass(&mut self, allow_pass: bool) {
+        self.allow_pass = allow_pass;
+    }
+
+    /// let `seat` lead the current trick, e.g. to set up a puzzle without playing earlier tricks.
+    /// Only allowed after trump is picked and before anyone played to the trick.
+    /// A [`crate::history::RoundHistory`] doesn't record this, and replays with the regular leaders
+    pub fn set_leader(&mut self, seat: usize) {
+        assert!(self.phase == RoundPhase::PlayCards);
+        assert!(self.trick.is_empty());
+        assert!(seat < 4);
+
+        self.turn = seat;
+    }
+
+    /// make the team picking trump score more than `threshold` card points to
+    /// win the round, instead of just more than half. Doesn't apply to a solo or misère
+    pub const fn set_contract_threshold(&mut self, threshold: i16) {
+        self.contract_threshold = threshold;
+    }
+
+    /// the player that picked trump, or is picking it
+    const fn trump_chooser(&self) -> usize {
+        (self.dealer + self.passes as usize) % 4
+    }
+
+    /// the card points `team` has to score more than to win the round, taking the
+    /// contract threshold into account. When neither team does, the round is a draw
+    fn points_needed(&self, team: usize) -> i16 {
+        let half = self.points_in_play() / 2;
+
+        match team == self.side_of(self.trump_chooser()) {
+            true => half.max(self.contract_threshold),
+            false => half.min(self.points_in_play() - self.contract_threshold),
+        }
+    }
+
+    /// truncate rollouts after `depth` actions, `None` always plays until the end of the round
+    pub const fn set_rollout_depth(&mut self, depth: Option<usize>) {
+        self.rollout_depth = depth;
+    }
+
+    pub fn set_legality_modifier(&mut self, modifier: Option<&'static dyn LegalityModifier>) {
+        self.legality_modifier = modifier;
+    }
+
+    pub fn set_rollout_policy(&mut self, policy: Option<&'static dyn RolloutPolicy>) {
+        self.rollout_policy = policy;
+    }
+
+    /// estimated reward of a round that might not be finished yet,
+    /// based on the margin in card points. A solo or misère only counts
+    /// once the declarer has lost. A deal without card points is a draw
+    pub fn heuristic_reward(&self, perspective: usize) -> f32 {
+        let team = self.side_of(perspective);
+
+        match self.contract.declarer() {
+            Some(declarer) => {
+                match (self.declarer_lost(declarer), team == self.side_of(declarer)) {
+                    (false, _) => 0.,
+                    (true, true) => -1.,
+                    (true, false) => 1.,
+                }
+            }
+            None if self.points_in_play() == 0 => 0.,
+            None => {
+                (self.scores[team] - self.scores[1 - team]) as f32 / self.points_in_play() as f32
+            }
+        }
+    }
+
+    /// whether the declarer of a solo or misère can't win anymore
+    fn declarer_lost(&self, declarer: usize) -> bool {
+        match self.contract {
+            Contract::Misere { .. } => self.tricks[declarer] > 0,
+            _ => self.tricks.iter().sum::<u8>() > self.tricks[declarer],
+        }
+    }
+
+    /// whether the winner of this round is already known: one team has more card
+    /// points than the other can still reach, or the declarer of a solo or misère lost
+    pub fn is_decided(&self) -> bool {
+        if self.is_terminal() {
+            return true;
+        }
+
+        match self.contract.declarer() {
+            Some(declarer) => self.declarer_lost(declarer),
+            // the other team can't reach what it needs anymore once one team has more
+            None => (0..2).any(|team| self.scores[team] > self.points_needed(team)),
+        }
+    }
+
+    /// the winning team of this round and the points they earn,
+    /// a solo or misère is always worth 30 points
+    pub fn result(&self) -> (usize, i16) {
+        assert!(self.is_terminal());
+
+        match self.contract.declarer() {
+            Some(declarer) => {
+                let declarer_team = self.side_of(declarer);
+                if self.declarer_won(declarer) {
+                    (declarer_team, 30)
+                } else {
+                    (1 - declarer_team, 30)
+                }
+            }
+            None => {
+                let winning_team = if self.scores[0] > self.points_needed(0) {
+                    0
+                } else {
+                    1
+                };
+                (
+                    winning_team,
+                    self.scores[winning_team] - self.points_needed(winning_team),
+                )
+            }
+        }
+    }
+
+    /// 1 for a win, 0 for a draw and -1 for a loss, from the perspective of `perspective`
+    pub fn outcome_sign(&self, perspective: usize) -> i8 {
+        assert!(self.is_terminal());
+
+        let team = self.side_of(perspective);
+
+        match self.contract.declarer() {
+            Some(declarer) => {
+                if self.declarer_won(declarer) == (team == self.side_of(declarer)) {
+                    1
+                } else {
+                    -1
+                }
+            }
+            None => (self.scores[team] - self.points_needed(team)).signum() as i8,
+        }
+    }
+
     fn possible_card_actions(&self) -> <Self as State>::ActionList {
         let mut cards = self.player_cards[self.turn];
 
@@ -191,9 +635,9 @@ impl Round {
 
         // this also means we're not the first player, i.e. the suit
         // to follow has been determined
-        if let Some((winning_card, winning_player)) = self.trick.winner() {
+        if let Some((winning_card, _)) = self.trick.winner() {
             // our team isn't winning
-            if winning_player % 2 != self.turn % 2 {
+            if !self.partner_winning() {
                 // have to buy if possible, but can't 'under-buy', except if that's our only possible move
                 if let Some(trump) = self.trick.trump() {
                     let mut mask = Stack::all_above(winning_card) & winning_card.suit().mask();
@@ -221,14 +665,26 @@ impl Round {
             }
         }
 
+        if let Some(modifier) = self.legality_modifier {
+            cards = modifier.restrict(self, cards);
+        }
+
+        // the filters above only apply when they leave something to play
+        debug_assert!(!cards.is_empty() || self.player_cards[self.turn].is_empty());
+
         ActionCollection::Cards(cards)
     }
 
     fn possible_trump_actions(&self) -> <Self as State>::ActionList {
-        let cards = self.player_cards[self.dealer];
-        let mut bits = 1 << 4;
+        let cards = self.cards_to_move();
+        // playing without trump, solo or misère is always possible
+        let mut bits = 1 << 4 | 1 << 5 | 1 << 6;
+
+        if self.allow_pass && self.passes < 3 {
+            bits |= 1 << 7;
+        }
 
-        for suit in [Suit::Spades, Suit::Clubs, Suit::Hearts, Suit::Diamonds] {
+        for suit in Suit::ALL {
             if cards.has_suit(suit) {
                 bits |= 1 << suit as u8;
             }
@@ -241,103 +697,1701 @@ impl Round {
         self.player_cards[player]
     }
 
-    pub fn unplayed_cards(&self) -> Stack {
-        !self.played_cards
+    /// the trumps the player picking trump can choose from, `None` playing without trump.
+    /// Empty when trump is already picked
+    pub fn legal_trumps(&self) -> Vec<Option<Suit>> {
+        if self.phase != RoundPhase::PickTrump {
+            return vec![];
+        }
+
+        self.possible_trump_actions()
+            .to_vec()
+            .into_iter()
+            .filter_map(|action| match action {
+                Action::PickTrump(trump) => Some(trump),
+                _ => None,
+            })
+            .collect()
     }
 
-    pub const fn played_cards(&self) -> Stack {
-        self.played_cards
+    /// a quick score for every suit the player picking trump holds, best first:
+    /// a point per card, one and a half for every ace or ten, and a tenth of the card points.
+    /// A lot cheaper than [`crate::analysis::evaluate_trumps`]
+    pub fn rank_trump_candidates(&self) -> Vec<(Suit, f32)> {
+        let cards = self.cards_to_move();
+        let mut candidates = cards
+            .by_suit()
+            .into_iter()
+            .zip(Suit::ALL)
+            .filter(|(of_suit, _)| !of_suit.is_empty())
+            .map(|(of_suit, suit)| {
+                let top_cards = (of_suit & (stack::TENS | stack::ACES)).len();
+                let points = of_suit.into_iter().map(|card| card.score()).sum::<i16>();
+                let score = of_suit.len() as f32 + 1.5 * top_cards as f32 + 0.1 * points as f32;
+
+                (suit, score)
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates
     }
 
-    pub const fn dealer(&self) -> usize {
-        self.dealer
+    /// replay the actions of `delta` on this state, see [`crate::history::RoundHistory::diff`]
+    pub fn apply_delta(&self, delta: &StateDelta) -> Round {
+        let mut round = *self;
+
+        for &action in delta.actions() {
+            debug_assert!(round.is_legal(&action));
+            round.apply_action(action);
+        }
+
+        round
     }
 
-    pub const fn phase(&self) -> RoundPhase {
-        self.phase
+    pub const fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            turn: self.turn,
+            player_cards: self.player_cards,
+            played_cards: self.played_cards,
+            scores: self.scores,
+            tricks: self.tricks,
+            voids: self.voids,
+            trick: self.trick,
+            contract: self.contract,
+            phase: self.phase,
+            passes: self.passes,
+        }
     }
 
-    pub const fn trump(&self) -> Option<Suit> {
-        self.trick.trump()
+    /// go back to the state of `snapshot`, which has to be taken from this round
+    pub const fn restore(&mut self, snapshot: &Snapshot) {
+        self.turn = snapshot.turn;
+        self.player_cards = snapshot.player_cards;
+        self.played_cards = snapshot.played_cards;
+        self.scores = snapshot.scores;
+        self.tricks = snapshot.tricks;
+        self.voids = snapshot.voids;
+        self.trick = snapshot.trick;
+        self.contract = snapshot.contract;
+        self.phase = snapshot.phase;
+        self.passes = snapshot.passes;
     }
 
-    pub fn suit_to_follow(&self) -> Option<Suit> {
-        self.trick.suit_to_follow()
+    /// legality only depends on the state of the round
+    pub fn is_legal(&self, action: &Action) -> bool {
+        self.possible_actions().has(action)
     }
 
-    pub const fn trick_ref(&self) -> &Trick {
-        &self.trick
+    /// the number of remaining tricks each team takes when everyone plays perfectly,
+    /// knowing all hands. Only feasible near the end of a round
+    pub fn solve_double_dummy(&self) -> [u8; 2] {
+        assert!(self.phase == RoundPhase::PlayCards);
+
+        let remaining = (self.num_tricks() - self.tricks_played()) as u8;
+        let taken = self.team_tricks(0);
+        let team_0 = self.double_dummy_search(0, u8::MAX) - taken;
+
+        [team_0, remaining - team_0]
     }
 
-    pub const fn scores(&self) -> [i16; 2] {
-        self.scores
+    /// minimax with alpha-beta pruning over the tricks team 0 ends up with
+    fn double_dummy_search(&self, mut alpha: u8, mut beta: u8) -> u8 {
+        if self.is_terminal() {
+            return self.team_tricks(0);
+        }
+
+        let maximizing = self.side_of(self.turn) == 0;
+        let mut best = if maximizing { 0 } else { u8::MAX };
+
+        for action in self.possible_actions().to_vec() {
+            let mut next = *self;
+            next.apply_action(action);
+            let value = next.double_dummy_search(alpha, beta);
+
+            if maximizing {
+                best = best.max(value);
+                alpha = alpha.max(value);
+            } else {
+                best = best.min(value);
+                beta = beta.min(value);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
     }
-}
 
-impl State for Round {
-    type Action = Action;
-    type ActionList = ActionCollection;
-    type Inference = Inference;
+    /// the number of tricks taken by `team` so far, see [`Round::side_of`]
+    fn team_tricks(&self, team: usize) -> u8 {
+        (0..4)
+            .filter(|&i| self.side_of(i) == team)
+            .map(|i| self.tricks[i])
+            .sum()
+    }
 
-    fn turn(&self) -> usize {
-        match self.phase {
-            RoundPhase::PickTrump => self.dealer,
-            RoundPhase::PlayCards => self.turn,
+    /// the action a search of `search_time` ms recommends to `observer`, who has to be
+    /// the player to move, and its estimated chance of winning between 0 and 1
+    pub fn suggest(&self, observer: usize, search_time: u128) -> (Action, f32) {
+        assert_eq!(observer, self.turn());
+
+        let result = Searcher::default().search(self, &Inference::default(), search_time);
+        let action = result.best_action.unwrap();
+        let avg_score = result
+            .child_stats
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map_or(0., |(stats, _)| stats.avg_score);
+
+        (action, ((avg_score + 1.) / 2.).clamp(0., 1.))
+    }
+
+    /// play uniformly random actions drawn from `rng` until the end of the round,
+    /// ignoring the rollout policy and depth, and return the reward for `perspective`
+    pub fn playout_with(&mut self, rng: &mut impl RngLike, perspective: usize) -> f32 {
+        while !self.is_terminal() {
+            let actions = self.possible_actions().to_vec();
+            self.apply_action(actions[rng.mod_usize(actions.len())]);
         }
+
+        self.reward(perspective)
     }
 
-    fn randomize(&self, observer: usize, inference: &Self::Inference) -> Self {
-        let observer_cards = self.player_cards[observer];
-        let player_card_counts = std::array::from_fn(|i| self.player_cards[i].len() as usize);
+    /// whether `seat` is to move, can't follow and has to give up a card worth points,
+    /// because every card it may play is a jack or higher
+    pub fn is_squeezed(&self, seat: usize) -> bool {
+        if self.phase != RoundPhase::PlayCards || seat != self.turn {
+            return false;
+        }
 
-        self.randomize_for(
-            observer,
-            observer_cards,
-            self.played_cards,
-            player_card_counts,
-            inference,
-        )
+        match self.suit_to_follow() {
+            Some(suit) if !self.can_follow(seat, suit) => self
+                .possible_card_actions()
+                .to_vec()
+                .into_iter()
+                .all(|action| matches!(action, Action::PlayCard(card) if card.score() > 0)),
+            _ => false,
+        }
     }
 
-    fn possible_actions(&self) -> Self::ActionList {
-        match self.phase {
-            RoundPhase::PickTrump => self.possible_trump_actions(),
-            RoundPhase::PlayCards => self.possible_card_actions(),
+    /// like [`State::apply_action`], but reports whether this finished a trick or picked trump
+    pub fn apply_action_observed(&mut self, action: Action) -> ApplyOutcome {
+        let phase = self.phase;
+        let tricks_played = self.tricks_played();
+        let scores = self.scores;
+
+        self.apply_action(action);
+
+        let trick_finished = self.tricks_played() > tricks_played;
+        let points = self.scores.iter().sum::<i16>() - scores.iter().sum::<i16>();
+
+        ApplyOutcome {
+            trick_finished,
+            round_phase_changed: self.phase != phase,
+            winner: trick_finished.then_some((self.turn, points as u8)),
         }
     }
 
-    fn apply_action(&mut self, action: Self::Action) {
-        match action {
-            Action::PlayCard(card) => self.play_card(card),
-            Action::PickTrump(trump) => self.set_trump(trump),
+    /// the number of different ways to finish this round by legal moves, by brute force.
+    /// This grows exponentially with the number of cards left, a single trick can
+    /// already have 8^4 continuations, so only use it to verify endgames of about 3 cards
+    pub fn count_leaves(&self) -> u64 {
+        if self.is_terminal() {
+            return 1;
         }
+
+        self.possible_actions()
+            .to_vec()
+            .into_iter()
+            .map(|action| {
+                let mut next = *self;
+                next.apply_action(action);
+                next.count_leaves()
+            })
+            .sum()
     }
 
-    fn is_terminal(&self) -> bool {
-        self.played_cards == Stack::ALL
+    /// whether the trick is currently won by a player on the side of the player to move
+    pub fn partner_winning(&self) -> bool {
+        self.trick
+            .winner()
+            .is_some_and(|(_, winner)| self.side_of(winner) == self.side_of(self.turn))
     }
 
-    fn reward(&self, perspective: usize) -> f32 {
-        assert!(self.is_terminal());
+    /// whether the player to move has only one legal action
+    pub fn is_forced(&self) -> bool {
+        self.possible_actions().len() == 1
+    }
 
-        let team = perspective % 2;
-        (self.scores[team] - 30) as f32 / 30.
+    /// the legal actions of the next player after `action` would be applied
+    pub fn legal_after(&self, action: Action) -> ActionCollection {
+        assert!(self.is_legal(&action));
+
+        let mut round = *self;
+        round.apply_action(action);
+        round.possible_actions()
     }
-}
 
-impl Debug for Round {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..4 {
-            writeln!(f, "player {i}: {:?}", self.player_cards[i])?;
+    /// for every legal action, the winner of the trick and its score
+    /// if that action finishes the current trick
+    pub fn immediate_trick_outcome(&self) -> Vec<(Action, Option<(usize, i16)>)> {
+        self.possible_actions()
+            .to_vec()
+            .into_iter()
+            .map(|action| match action {
+                Action::PlayCard(card) if self.cards_in_current_trick() == 3 => {
+                    let mut trick = self.trick;
+                    trick.play(card, self.turn);
+
+                    let (_, winner) = trick.winner().unwrap();
+                    (action, Some((winner, trick.score())))
+                }
+                _ => (action, None),
+            })
+            .collect()
+    }
+
+    /// the winning team and score of the current trick
+    /// when `policy` plays the remaining cards of it.
+    /// Only allowed while there are cards left to play
+    pub fn predict_trick(&self, policy: &dyn RolloutPolicy) -> (usize, i16) {
+        assert!(self.phase == RoundPhase::PlayCards);
+        assert!(!self.is_terminal(), "no trick left to predict");
+
+        let mut round = *self;
+        let tricks_played = self.tricks_played();
+        let score = self.scores[0] + self.scores[1];
+
+        while round.tricks_played() == tricks_played {
+            let action = policy.choose(&round);
+            round.apply_action(action);
         }
 
-        f.debug_struct("Round")
-            .field("turn", &self.turn)
-            .field("dealer", &self.dealer)
-            .field("played_cards", &self.played_cards)
-            .field("trick", &self.trick)
-            .field("scores", &self.scores)
-            .field("phase", &self.phase)
-            .finish()
+        // the winner of a trick leads the next one
+        let winner = round.turn;
+        (
+            round.side_of(winner),
+            round.scores[0] + round.scores[1] - score,
+        )
+    }
+
+    /// the legal cards that win the current trick for the player to move, whatever the
+    /// others still play. This is exact for the last card of a trick, before that
+    /// a card only counts when none of the cards held by others could beat it
+    pub fn guaranteed_winners(&self) -> Stack {
+        let ActionCollection::Cards(cards) = self.possible_actions() else {
+            return Stack::default();
+        };
+        let trump = self.trump();
+        let last_card = self.cards_in_current_trick() == 3;
+        let others = self.unplayed_cards() & !self.cards_to_move();
+
+        cards
+            .into_iter()
+            .filter(|card| {
+                let led = self.suit_to_follow().unwrap_or(card.suit());
+                let beats_winner = self
+                    .trick
+                    .winner()
+                    .is_none_or(|(winning_card, _)| card.beats(&winning_card, led, trump));
+
+                beats_winner
+                    && (last_card
+                        || others
+                            .into_iter()
+                            .all(|other| !other.beats(card, led, trump)))
+            })
+            .collect()
+    }
+
+    /// the cards of `seat` that are the highest remaining card of their suit.
+    /// When other players could still trump them, only trumps are counted
+    pub fn boss_cards(&self, seat: usize) -> Stack {
+        let cards = self.player_cards[seat];
+        let unplayed = self.unplayed_cards();
+        let mut bosses = Stack::default();
+
+        for card in cards.into_iter() {
+            if unplayed.of_suit(card.suit()).above(card).len() == 1 {
+                bosses.push(card);
+            }
+        }
+
+        if let Some(trump) = self.trump() {
+            if !(unplayed & !cards).of_suit(trump).is_empty() {
+                bosses = bosses.of_suit(trump);
+            }
+        }
+
+        bosses
+    }
+
+    /// whether `player` failed to follow `suit` earlier this round
+    pub const fn is_void(&self, player: usize, suit: Suit) -> bool {
+        self.voids[player] & 1 << suit as u8 != 0
+    }
+
+    /// whether `seat` holds a card of `suit`, which other players can't know
+    pub(crate) const fn can_follow(&self, seat: usize, suit: Suit) -> bool {
+        self.player_cards[seat].has_suit(suit)
+    }
+
+    /// whether `seat` can still follow `suit` as far as `observer` knows:
+    /// `seat` hasn't shown to be void and `observer` doesn't hold all remaining cards of it
+    pub fn might_follow(&self, observer: usize, seat: usize, suit: Suit) -> bool {
+        if seat == observer {
+            return self.can_follow(seat, suit);
+        }
+
+        let outstanding = (self.unplayed_cards() & !self.player_cards[observer]).of_suit(suit);
+        !self.is_void(seat, suit) && !outstanding.is_empty()
+    }
+
+    /// the highest card of `suit` that hasn't been played and isn't in the hand of
+    /// `seat`, i.e. the card another player might beat the cards of `seat` with
+    pub fn top_outstanding(&self, seat: usize, suit: Suit) -> Option<Card> {
+        (self.unplayed_cards() & !self.player_cards[seat]).highest_of_suit(suit)
+    }
+
+    /// how many opponents of the player to move could still follow when they lead `suit`,
+    /// based only on public knowledge: the opponents that haven't shown to be void,
+    /// limited by the number of cards of that suit we don't hold ourselves
+    pub fn min_following_possible(&self, suit: Suit) -> usize {
+        let seat = self.turn;
+        let outstanding = (self.unplayed_cards() & !self.player_cards[seat])
+            .of_suit(suit)
+            .len() as usize;
+        let candidates = (0..4)
+            .filter(|&i| self.side_of(i) != self.side_of(seat) && !self.is_void(i, suit))
+            .count();
+
+        candidates.min(outstanding)
+    }
+
+    /// the hand of the player to move
+    pub fn cards_to_move(&self) -> Stack {
+        self.player_cards[self.turn()]
+    }
+
+    /// the card points of all cards that haven't been played yet, note that
+    /// the cards of the current trick are played, but not counted in the scores yet
+    pub fn points_remaining(&self) -> i16 {
+        self.unplayed_cards()
+            .into_iter()
+            .map(|card| card.score())
+            .sum()
+    }
+
+    /// the cards that are still in the hands of the players
+    pub fn unplayed_cards(&self) -> Stack {
+        !(self.played_cards | self.undealt)
+    }
+
+    pub const fn played_cards(&self) -> Stack {
+        self.played_cards
+    }
+
+    pub const fn dealer(&self) -> usize {
+        self.dealer
+    }
+
+    pub const fn phase(&self) -> RoundPhase {
+        self.phase
+    }
+
+    /// `None` while trump hasn't been picked yet, or when playing without trump
+    pub const fn trump(&self) -> Option<Suit> {
+        match self.phase {
+            RoundPhase::PickTrump => None,
+            RoundPhase::PlayCards => self.trick.trump(),
+        }
+    }
+
+    pub const fn contract(&self) -> Contract {
+        self.contract
+    }
+
+    pub const fn tricks(&self) -> [u8; 4] {
+        self.tricks
+    }
+
+    /// the seats in the order they play in the current trick, starting with the leader
+    pub fn trick_order(&self) -> [usize; 4] {
+        let leader = self.trick.leader().unwrap_or(self.turn);
+        std::array::from_fn(|i| (leader + i) % 4)
+    }
+
+    /// number of completed tricks
+    pub const fn tricks_played(&self) -> usize {
+        self.played_cards.len() as usize / 4
+    }
+
+    pub const fn cards_in_current_trick(&self) -> usize {
+        self.trick.len()
+    }
+
+    pub fn suit_to_follow(&self) -> Option<Suit> {
+        self.trick.suit_to_follow()
+    }
+
+    pub const fn trick_ref(&self) -> &Trick {
+        &self.trick
+    }
+
+    pub const fn scores(&self) -> [i16; 2] {
+        self.scores
+    }
+}
+
+impl State for Round {
+    type Action = Action;
+    type ActionList = ActionCollection;
+    type Inference = Inference;
+
+    fn turn(&self) -> usize {
+        match self.phase {
+            RoundPhase::PickTrump => self.trump_chooser(),
+            RoundPhase::PlayCards => self.turn,
+        }
+    }
+
+    fn randomize(&self, observer: usize, inference: &Self::Inference) -> Self {
+        let observer_cards = self.player_cards[observer];
+        let player_card_counts = std::array::from_fn(|i| self.player_cards[i].len() as usize);
+
+        self.randomize_for(
+            observer,
+            observer_cards,
+            self.played_cards,
+            player_card_counts,
+            inference,
+        )
+    }
+
+    fn possible_actions(&self) -> Self::ActionList {
+        match self.phase {
+            RoundPhase::PickTrump => self.possible_trump_actions(),
+            RoundPhase::PlayCards => self.possible_card_actions(),
+        }
+    }
+
+    fn apply_action(&mut self, action: Self::Action) {
+        match action {
+            Action::PlayCard(card) => self.play_card(card),
+            Action::PickTrump(trump) => self.set_contract(Contract::from(trump)),
+            Action::PickSolo => self.set_contract(Contract::Solo {
+                declarer: self.turn(),
+            }),
+            Action::PickMisere => self.set_contract(Contract::Misere {
+                declarer: self.turn(),
+            }),
+            Action::PassTrump => self.passes += 1,
+        }
+    }
+
+    fn do_rollout(&mut self) {
+        let mut depth = 0;
+
+        while !self.is_terminal() && self.rollout_depth.is_none_or(|d| depth < d) {
+            let action = match self.rollout_policy {
+                Some(policy) => policy.choose(self),
+                None => self.possible_actions().pop_random().unwrap(),
+            };
+            self.apply_action(action);
+            depth += 1;
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.played_cards | self.undealt == Stack::ALL
+    }
+
+    fn reward(&self, perspective: usize) -> f32 {
+        if !self.is_terminal() {
+            assert!(self.rollout_depth.is_some());
+            return self.heuristic_reward(perspective);
+        }
+
+        match self.contract.declarer() {
+            Some(_) => self.outcome_sign(perspective) as f32,
+            // only sevens, eights and nines were dealt, nobody can score
+            None if self.points_in_play() == 0 => 0.,
+            None => {
+                let half = self.points_in_play() as f32 / 2.;
+                let team = self.side_of(perspective);
+                let margin = (self.scores[team] - self.points_needed(team)) as f32 / half;
+
+                margin.clamp(-1., 1.)
+            }
+        }
+    }
+}
+
+/// rollout policies and legality modifiers are compared by address
+impl PartialEq for Round {
+    fn eq(&self, other: &Self) -> bool {
+        let same_policy = match (self.rollout_policy, other.rollout_policy) {
+            (Some(a), Some(b)) => std::ptr::addr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        let same_modifier = match (self.legality_modifier, other.legality_modifier) {
+            (Some(a), Some(b)) => std::ptr::addr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+
+        self.turn == other.turn
+            && self.dealer == other.dealer
+            && self.player_cards == other.player_cards
+            && self.played_cards == other.played_cards
+            && self.undealt == other.undealt
+            && self.scores == other.scores
+            && self.tricks == other.tricks
+            && self.voids == other.voids
+            && self.trick == other.trick
+            && self.contract == other.contract
+            && self.phase == other.phase
+            && self.allow_pass == other.allow_pass
+            && self.contract_threshold == other.contract_threshold
+            && self.passes == other.passes
+            && self.rollout_depth == other.rollout_depth
+            && same_policy
+            && same_modifier
+    }
+}
+
+impl Eq for Round {}
+
+impl Debug for Round {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..4 {
+            writeln!(f, "player {i}: {:?}", self.player_cards[i])?;
+        }
+
+        f.debug_struct("Round")
+            .field("turn", &self.turn)
+            .field("dealer", &self.dealer)
+            .field("played_cards", &self.played_cards)
+            .field("trick", &self.trick)
+            .field("scores", &self.scores)
+            .field("tricks", &self.tricks)
+            .field("voids", &self.voids)
+            .field("contract", &self.contract)
+            .field("phase", &self.phase)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ismcts::{action_list::ActionList, state::State};
+
+    use super::{partner_of, team_of, ApplyOutcome, InvalidDeal, Round, RoundPhase};
+    use crate::{
+        action::Action, card::Card, contract::Contract, inference::Inference, rng, rng::SeededRng,
+        rollout::GreedyRollout, stack::Stack, suit::Suit,
+    };
+
+    fn finished_round(contract: Contract, tricks: [u8; 4]) -> Round {
+        Round {
+            contract,
+            played_cards: Stack::ALL,
+            tricks,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_solo_reward() {
+        let round = finished_round(Contract::Solo { declarer: 1 }, [0, 8, 0, 0]);
+
+        assert_eq!(round.reward(1), 1.);
+        assert_eq!(round.reward(0), -1.);
+        assert_eq!(round.reward(2), -1.);
+        assert_eq!(round.reward(3), -1.);
+        assert_eq!(round.result(), (1, 30));
+    }
+
+    #[test]
+    fn test_failed_solo_reward() {
+        let round = finished_round(Contract::Solo { declarer: 1 }, [1, 7, 0, 0]);
+
+        assert_eq!(round.reward(1), -1.);
+        assert_eq!(round.reward(0), 1.);
+        assert_eq!(round.reward(2), 1.);
+        assert_eq!(round.reward(3), 1.);
+        assert_eq!(round.result(), (0, 30));
+    }
+
+    #[test]
+    fn test_misere_reward() {
+        let round = finished_round(Contract::Misere { declarer: 2 }, [3, 2, 0, 3]);
+
+        assert_eq!(round.reward(2), 1.);
+        assert_eq!(round.reward(0), -1.);
+        assert_eq!(round.reward(1), -1.);
+        assert_eq!(round.result(), (0, 30));
+    }
+
+    #[test]
+    fn test_failed_misere_reward() {
+        for tricks in 1..=8 {
+            let round =
+                finished_round(Contract::Misere { declarer: 2 }, [0, 8 - tricks, tricks, 0]);
+
+            assert_eq!(round.reward(2), -1.);
+            assert_eq!(round.reward(0), 1.);
+            assert_eq!(round.reward(3), 1.);
+            assert_eq!(round.result(), (1, 30));
+        }
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let hands: [Stack; 4] = std::array::from_fn(|i| Stack::ALL.of_suit(Suit::from(i as u8)));
+        let round = Round::from_parts(0, hands, Stack::default()).unwrap();
+        assert_eq!(round, Round::from_deal(0, hands));
+
+        let mut duplicate = hands;
+        duplicate[1].push(Card::new(0));
+        assert_eq!(
+            Round::from_parts(0, duplicate, Stack::default()),
+            Err(InvalidDeal::Duplicate(Card::new(0)))
+        );
+        assert_eq!(
+            Round::from_parts(0, hands, Stack::from_slice(&[31])),
+            Err(InvalidDeal::Duplicate(Card::new(31)))
+        );
+
+        let mut missing = hands;
+        missing[2] &= !(1 << 20);
+        assert_eq!(
+            Round::from_parts(0, missing, Stack::default()),
+            Err(InvalidDeal::Missing(Stack::from_slice(&[20])))
+        );
+        assert_eq!(
+            Round::from_parts(0, missing, Stack::from_slice(&[20])),
+            Err(InvalidDeal::UnequalHands)
+        );
+
+        // a single trick, the other cards aren't dealt and don't count as played
+        let last = [0, 8, 16, 24].map(|i| Stack::from_slice(&[i]));
+        let undealt = !Stack::from_slice(&[0, 8, 16, 24]);
+        let round = Round::from_parts(0, last, undealt).unwrap();
+        assert_eq!(round.num_tricks(), 1);
+        assert!(round.undealt_cards() == undealt);
+        assert!(round.played_cards().is_empty());
+    }
+
+    #[test]
+    fn test_suggest() {
+        let mut round = Round::new(1);
+
+        for _ in 0..2 {
+            let (action, confidence) = round.suggest(round.turn(), 10);
+            assert!(round.is_legal(&action));
+            assert!((0. ..=1.).contains(&confidence));
+
+            round.apply_action(action);
+            assert_eq!(round.phase(), RoundPhase::PlayCards);
+        }
+    }
+
+    #[test]
+    fn test_is_decided() {
+        let mut round = finished_round(Contract::NoTrump, [2, 2, 2, 2]);
+        round.scores = [31, 29];
+        assert!(round.is_decided());
+
+        // only the first 7 tricks are played, team 1 could still catch up with the tens
+        let mut lopsided = round;
+        lopsided.played_cards = !Stack::from_slice(&[3, 7, 15, 23, 31, 11, 19, 27]);
+        lopsided.scores = [32, 4];
+        assert!(lopsided.is_decided());
+
+        let mut balanced = lopsided;
+        balanced.scores = [22, 14];
+        assert!(!balanced.is_decided());
+
+        let mut misere = finished_round(Contract::Misere { declarer: 1 }, [0, 1, 0, 0]);
+        misere.played_cards = Stack::from_slice(&[0, 1, 2, 3]);
+        assert!(misere.is_decided());
+        misere.tricks = [1, 0, 0, 0];
+        assert!(!misere.is_decided());
+    }
+
+    #[test]
+    fn test_legal_trumps() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[9, 10, 17, 18, 19, 20, 30, 31]),
+            Stack::from_slice(&[8, 11, 12, 13, 14, 15, 16, 21]),
+            Stack::from_slice(&[22, 23, 24, 25, 26, 27, 28, 29]),
+        ];
+        let mut round = Round::from_deal(1, hands);
+        round.set_allow_pass(true);
+
+        // the dealer holds no spades
+        assert_eq!(
+            round.legal_trumps(),
+            vec![
+                Some(Suit::Clubs),
+                Some(Suit::Hearts),
+                Some(Suit::Diamonds),
+                None
+            ]
+        );
+
+        // and the next player only clubs and hearts
+        round.apply_action(Action::PassTrump);
+        assert_eq!(
+            round.legal_trumps(),
+            vec![Some(Suit::Clubs), Some(Suit::Hearts), None]
+        );
+
+        round.apply_action(Action::PickTrump(None));
+        assert!(round.legal_trumps().is_empty());
+    }
+
+    #[test]
+    fn test_playout_with() {
+        let round = Round::new(0);
+        let mut a = round;
+        let mut b = round;
+
+        let reward = a.playout_with(&mut SeededRng::new(5), 1);
+        romu::u64();
+        assert_eq!(b.playout_with(&mut SeededRng::new(5), 1), reward);
+        assert!(a.is_terminal());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_is_squeezed() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[11, 12, 13, 14, 15, 19, 20, 21]),
+            Stack::from_slice(&[8, 9, 10, 16, 17, 18, 30, 31]),
+            Stack::from_slice(&[22, 23, 24, 25, 26, 27, 28, 29]),
+        ];
+        let mut round = Round::from_deal(3, hands);
+        round.apply_action(Action::PickTrump(None));
+        assert!(!round.is_squeezed(0));
+
+        // player 1 has no spades and only cards worth points
+        round.apply_action(Action::PlayCard(Card::new(0)));
+        assert!(round.is_squeezed(1));
+        assert!(!round.is_squeezed(2));
+
+        // player 2 can't follow either, but can discard a low card
+        round.apply_action(Action::PlayCard(Card::new(11)));
+        assert!(!round.is_squeezed(2));
+    }
+
+    #[test]
+    fn test_apply_action_observed() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[9, 10, 17, 18, 19, 20, 30, 31]),
+            Stack::from_slice(&[8, 11, 12, 13, 14, 15, 16, 21]),
+            Stack::from_slice(&[22, 23, 24, 25, 26, 27, 28, 29]),
+        ];
+        let mut round = Round::from_deal(3, hands);
+
+        let outcome = round.apply_action_observed(Action::PickTrump(None));
+        assert!(outcome.round_phase_changed);
+        assert!(!outcome.trick_finished);
+
+        for index in [7, 9, 8] {
+            let outcome = round.apply_action_observed(Action::PlayCard(Card::new(index)));
+            assert_eq!(outcome, ApplyOutcome::default());
+        }
+
+        let outcome = round.apply_action_observed(Action::PlayCard(Card::new(24)));
+        assert!(outcome.trick_finished);
+        assert!(!outcome.round_phase_changed);
+        assert_eq!(outcome.winner, Some((0, 5)));
+    }
+
+    #[test]
+    fn test_degenerate_trump_hand() {
+        // the dealer holds a single card, or none at all
+        for dealer_cards in [Stack::from_slice(&[5]), Stack::default()] {
+            let hands = [
+                dealer_cards,
+                Stack::default(),
+                Stack::default(),
+                Stack::default(),
+            ];
+            let round = Round::from_deal(0, hands);
+
+            let actions = round.possible_actions();
+            assert!(actions.len() >= 1);
+            assert!(actions.has(&Action::PickTrump(None)));
+        }
+    }
+
+    #[test]
+    fn test_count_leaves() {
+        // everyone holds two cards of their own suit
+        let hands = [0, 8, 16, 24].map(|i| Stack::from_slice(&[i, i + 1]));
+        let undealt = !hands.into_iter().fold(Stack::default(), |a, b| a | b);
+        let mut round = Round::from_parts(3, hands, undealt).unwrap();
+        round.apply_action(Action::PickTrump(None));
+
+        // nobody can follow, so the first trick is any card of each player,
+        // after which the last trick is forced
+        assert_eq!(round.count_leaves(), 2 * 2 * 2 * 2);
+
+        round.apply_action(Action::PlayCard(Card::new(0)));
+        assert_eq!(round.count_leaves(), 2 * 2 * 2);
+
+        let mut finished = round;
+        while !finished.is_terminal() {
+            finished.apply_action(finished.possible_actions().pop_random().unwrap());
+        }
+        assert_eq!(finished.count_leaves(), 1);
+    }
+
+    #[test]
+    fn test_set_leader() {
+        let mut round = Round::new(0);
+        round.apply_action(Action::PickTrump(None));
+        assert_eq!(round.turn(), 1);
+
+        round.set_leader(2);
+        assert_eq!(round.turn(), 2);
+
+        let hand = round.player_cards(2).into_iter().map(Action::PlayCard);
+        assert_eq!(round.possible_actions().to_vec(), hand.collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_leader_mid_trick() {
+        let mut round = Round::new(0);
+        round.apply_action(Action::PickTrump(None));
+        round.apply_action(round.possible_actions().pop_random().unwrap());
+
+        round.set_leader(2);
+    }
+
+    #[test]
+    fn test_randomize_is_pure() {
+        let mut round = Round::new(2);
+        while round.tricks_played() < 3 {
+            round.apply_action(round.possible_actions().pop_random().unwrap());
+        }
+
+        // copying doesn't touch the generator
+        rng::seed_from(9);
+        let expected = romu::u64();
+        rng::seed_from(9);
+        let original = round;
+        assert_eq!(romu::u64(), expected);
+
+        for observer in 0..4 {
+            let randomized = round.randomize(observer, &Inference::default());
+            assert!(randomized.player_cards(observer) == round.player_cards(observer));
+            assert_eq!(round, original);
+        }
+    }
+
+    #[test]
+    fn test_rank_trump_candidates() {
+        let hands = [
+            Stack::from_slice(&[16, 17, 18, 19, 20, 8, 9, 10]),
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[11, 12, 13, 14, 15, 21, 22, 23]),
+            Stack::from_slice(&[24, 25, 26, 27, 28, 29, 30, 31]),
+        ];
+        let round = Round::from_deal(0, hands);
+        // five low hearts against three low clubs
+        let ranked = round.rank_trump_candidates();
+        assert_eq!(
+            ranked.iter().map(|r| r.0).collect::<Vec<_>>(),
+            [Suit::Hearts, Suit::Clubs]
+        );
+
+        // but the king, ace and ten of clubs beat five low hearts
+        let hands = [
+            Stack::from_slice(&[16, 17, 18, 19, 20, 13, 14, 15]),
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[8, 9, 10, 11, 12, 21, 22, 23]),
+            Stack::from_slice(&[24, 25, 26, 27, 28, 29, 30, 31]),
+        ];
+        let round = Round::from_deal(0, hands);
+        let ranked = round.rank_trump_candidates();
+        assert_eq!(ranked[0].0, Suit::Clubs);
+        assert!(ranked[0].1 > ranked[1].1);
+    }
+
+    #[test]
+    fn test_partner_winning() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[9, 10, 17, 18, 19, 20, 30, 31]),
+            Stack::from_slice(&[8, 11, 12, 13, 14, 15, 16, 21]),
+            Stack::from_slice(&[22, 23, 24, 25, 26, 27, 28, 29]),
+        ];
+        let mut round = Round::from_deal(3, hands);
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+        assert!(!round.partner_winning());
+
+        // player 0 leads the ten of spades, player 1 can't follow but trumps
+        round.apply_action(Action::PlayCard(Card::new(7)));
+        assert!(!round.partner_winning());
+        round.apply_action(Action::PlayCard(Card::new(17)));
+        assert!(!round.partner_winning());
+
+        // player 2 can't overtrump with the seven of hearts, so player 3's partner wins
+        round.apply_action(Action::PlayCard(Card::new(16)));
+        assert!(round.partner_winning());
+    }
+
+    #[test]
+    fn test_new_with_hand() {
+        let hand = Stack::from_slice(&[0, 7, 9, 14, 20, 23, 28, 31]);
+
+        for i in 0..200 {
+            let round = Round::new_with_hand(i % 4, 2, hand);
+            assert!(round.player_cards(2) == hand);
+
+            let mut seen = Stack::default();
+            for player in 0..4 {
+                assert_eq!(round.player_cards(player).len(), 8);
+                seen |= round.player_cards(player);
+            }
+            assert!(seen == Stack::ALL);
+        }
+    }
+
+    #[test]
+    fn test_never_without_card_actions() {
+        for i in 0..300 {
+            let mut round = Round::new(i % 4);
+            round.apply_action(round.possible_actions().pop_random().unwrap());
+
+            while !round.is_terminal() {
+                let mut actions = round.possible_actions();
+                assert!(actions.len() > 0, "no legal cards: {round:?}");
+                assert!(actions.to_vec().into_iter().all(|action| match action {
+                    Action::PlayCard(card) => round.cards_to_move().has_card(card),
+                    _ => false,
+                }));
+
+                round.apply_action(actions.pop_random().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_follow() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[8, 9, 10, 11, 12, 13, 14, 15]),
+            Stack::from_slice(&[16, 17, 18, 19, 20, 21, 22, 23]),
+            Stack::from_slice(&[24, 25, 26, 27, 28, 29, 30, 31]),
+        ];
+        let mut round = Round::from_deal(3, hands);
+        round.apply_action(Action::PickTrump(None));
+
+        // player 0 holds every spade, so nobody else can follow them
+        assert!(round.can_follow(0, Suit::Spades));
+        assert!(!round.can_follow(1, Suit::Spades));
+        assert!(round.might_follow(0, 0, Suit::Spades));
+        assert!(!round.might_follow(0, 1, Suit::Spades));
+
+        // without having seen a discard, the others might still hold clubs
+        assert!(!round.can_follow(2, Suit::Clubs));
+        assert!(round.might_follow(0, 2, Suit::Clubs));
+
+        round.apply_action(Action::PlayCard(Card::new(0)));
+        round.apply_action(Action::PlayCard(Card::new(8)));
+        assert!(round.might_follow(3, 2, Suit::Spades));
+        round.apply_action(Action::PlayCard(Card::new(16)));
+        assert!(!round.might_follow(3, 2, Suit::Spades));
+        assert!(round.might_follow(3, 2, Suit::Clubs));
+    }
+
+    #[test]
+    fn test_contract_threshold() {
+        // the dealer, seat 0, picked trump and their team scored a bare majority
+        let mut round = finished_round(Contract::Trump(Suit::Hearts), [2, 2, 2, 2]);
+        round.scores = [32, 28];
+        assert!(round.reward(0) > 0.);
+        assert!(round.reward(1) < 0.);
+
+        round.set_contract_threshold(35);
+        assert!(round.reward(0) < 0.);
+        assert!(round.reward(2) < 0.);
+        assert!(round.reward(1) > 0.);
+        assert_eq!(round.reward(0), -round.reward(1));
+
+        round.scores = [40, 20];
+        assert!(round.reward(0) > 0.);
+
+        // after a pass, seat 1 picked trump instead
+        round.passes = 1;
+        assert!(round.reward(1) < 0.);
+    }
+
+    #[test]
+    fn test_contract_threshold_result() {
+        let mut round = finished_round(Contract::Trump(Suit::Hearts), [2, 2, 2, 2]);
+        round.scores = [32, 28];
+        assert_eq!(round.result(), (0, 2));
+
+        // the declaring team has a majority, but not more than the threshold
+        round.set_contract_threshold(35);
+        assert_eq!(round.result(), (1, 3));
+        assert_eq!(round.outcome_sign(0), -1);
+        assert_eq!(round.outcome_sign(1), 1);
+
+        round.scores = [35, 25];
+        assert_eq!(round.outcome_sign(0), 0);
+        assert_eq!(round.reward(0), 0.);
+
+        round.scores = [36, 24];
+        assert_eq!(round.result(), (0, 1));
+
+        // team 0 has a majority already, but could still fall short of the threshold
+        round.played_cards = !Stack::from_slice(&[3, 7, 15, 23, 31, 11, 19, 27]);
+        round.scores = [32, 4];
+        assert!(!round.is_decided());
+        round.scores = [36, 0];
+        assert!(round.is_decided());
+    }
+
+    #[test]
+    fn test_zero_point_deal() {
+        // one seven to every player, nothing to score
+        let hands = [0, 8, 16, 24].map(|card| Stack::from_slice(&[card]));
+        let mut round = Round::from_deal(3, hands);
+        assert_eq!(round.points_in_play(), 0);
+
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+        assert_eq!(round.heuristic_reward(0), 0.);
+
+        while !round.is_terminal() {
+            let action = round.possible_actions().to_vec()[0];
+            round.apply_action(action);
+        }
+        assert_eq!(round.reward(0), 0.);
+        assert_eq!(round.reward(1), 0.);
+    }
+
+    #[test]
+    fn test_rollout_depth() {
+        let mut round = Round::new(0);
+        round.apply_action(Action::PickTrump(None));
+        round.scores = [10, 4];
+        round.set_rollout_depth(Some(0));
+
+        round.do_rollout();
+        assert_eq!(round.played_cards(), Stack::default());
+        assert_eq!(round.reward(0), 0.1);
+        assert_eq!(round.reward(1), -0.1);
+
+        round.set_rollout_depth(Some(5));
+        round.do_rollout();
+        assert_eq!(round.played_cards().len(), 5);
+
+        round.set_rollout_depth(None);
+        round.do_rollout();
+        assert!(round.is_terminal());
+    }
+
+    #[test]
+    fn test_boss_cards() {
+        // the ten of spades isn't dealt, but the ace and ten of clubs are still out
+        let hands = [
+            Stack::from_slice(&[6, 13, 23]),
+            Stack::from_slice(&[14, 24, 25]),
+            Stack::from_slice(&[15, 26, 27]),
+            Stack::from_slice(&[0, 1, 28]),
+        ];
+        let undealt = !hands.iter().fold(Stack::default(), |a, &b| a | b);
+        let mut round = Round::from_parts(0, hands, undealt).unwrap();
+
+        round.apply_action(Action::PickTrump(None));
+        assert!(round.boss_cards(0) == Stack::from_slice(&[6, 23]));
+
+        // the opponents could still trump the ace of spades
+        round.contract = Contract::Trump(Suit::Diamonds);
+        round.trick.set_trump(Some(Suit::Diamonds));
+        assert!(round.boss_cards(0).is_empty());
+    }
+
+    #[test]
+    fn test_top_outstanding() {
+        let hands = [
+            Stack::from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            Stack::from_slice(&[8, 9, 10, 11, 12, 13, 14, 15]),
+            Stack::from_slice(&[16, 17, 18, 19, 20, 21, 22, 23]),
+            Stack::from_slice(&[24, 25, 26, 27, 28, 29, 30, 31]),
+        ];
+        let mut round = Round::from_deal(3, hands);
+        round.apply_action(Action::PickTrump(None));
+
+        // player 0 holds every spade
+        assert_eq!(round.top_outstanding(0, Suit::Spades), None);
+        assert_eq!(round.top_outstanding(0, Suit::Clubs), Some(Card::new(15)));
+        assert_eq!(round.top_outstanding(1, Suit::Clubs), None);
+        assert_eq!(round.top_outstanding(1, Suit::Spades), Some(Card::new(7)));
+
+        for index in [7, 15, 23, 31] {
+            round.apply_action(Action::PlayCard(Card::new(index)));
+        }
+        assert_eq!(round.top_outstanding(0, Suit::Clubs), Some(Card::new(14)));
+        assert_eq!(round.top_outstanding(2, Suit::Spades), Some(Card::new(6)));
+
+        for index in [6, 14, 22, 30] {
+            round.apply_action(Action::PlayCard(Card::new(index)));
+        }
+        assert_eq!(round.top_outstanding(0, Suit::Clubs), Some(Card::new(13)));
+        assert_eq!(
+            round.top_outstanding(0, Suit::Diamonds),
+            Some(Card::new(29))
+        );
+        assert_eq!(round.top_outstanding(3, Suit::Diamonds), None);
+    }
+
+    #[test]
+    fn test_legal_after() {
+        let mut round = Round::new(3);
+        round.player_cards[0] = Stack::from_slice(&[2, 12, 13, 20]);
+        round.player_cards[1] = Stack::from_slice(&[5, 9, 10, 31]);
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+
+        let actions = round.legal_after(Action::PlayCard(Card::new(12)));
+        assert_eq!(
+            actions.to_vec(),
+            vec![
+                Action::PlayCard(Card::new(9)),
+                Action::PlayCard(Card::new(10))
+            ]
+        );
+        // the round itself is left untouched
+        assert_eq!(round.player_cards(0).len(), 4);
+    }
+
+    #[test]
+    fn test_is_legal() {
+        let mut round = Round::new(3);
+        round.player_cards[1] = Stack::from_slice(&[1, 5, 10, 20]);
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+        round.apply_action(Action::PlayCard(Card::new(3)));
+
+        // has to follow and buy the jack of spades
+        assert!(round.is_legal(&Action::PlayCard(Card::new(5))));
+        assert!(!round.is_legal(&Action::PlayCard(Card::new(1))));
+        assert!(!round.is_legal(&Action::PlayCard(Card::new(10))));
+        assert!(!round.is_legal(&Action::PlayCard(Card::new(20))));
+        // not in their hand
+        assert!(!round.is_legal(&Action::PlayCard(Card::new(6))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_legal_after_illegal() {
+        let mut round = Round::new(3);
+        round.player_cards[0] = Stack::from_slice(&[2, 12]);
+        round.apply_action(Action::PickTrump(None));
+
+        round.legal_after(Action::PlayCard(Card::new(3)));
+    }
+
+    #[test]
+    fn test_tricks_played() {
+        let mut round = Round::new(1);
+        round.apply_action(Action::PickTrump(None));
+
+        for i in 0..32 {
+            assert_eq!(round.tricks_played(), i / 4);
+            assert_eq!(round.cards_in_current_trick(), i % 4);
+
+            round.apply_action(round.possible_actions().pop_random().unwrap());
+        }
+
+        assert_eq!(round.tricks_played(), 8);
+        assert_eq!(round.cards_in_current_trick(), 0);
+    }
+
+    #[test]
+    fn test_immediate_trick_outcome() {
+        let mut round = Round::new(3);
+        round.player_cards[3] = Stack::from_slice(&[4, 7, 20]);
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+
+        // seven of spades, ace of spades, eight of spades
+        for index in [0, 6, 1] {
+            assert!(round
+                .immediate_trick_outcome()
+                .iter()
+                .all(|(_, outcome)| outcome.is_none()));
+            round.apply_action(Action::PlayCard(Card::new(index)));
+        }
+
+        // partner is winning, so there's no need to buy
+        let outcome = round.immediate_trick_outcome();
+        assert_eq!(
+            outcome,
+            vec![
+                (Action::PlayCard(Card::new(4)), Some((1, 6))),
+                (Action::PlayCard(Card::new(7)), Some((3, 9)))
+            ]
+        );
+
+        // without having to follow, trumping wins the trick
+        round.player_cards[3] = Stack::from_slice(&[12, 20]);
+        let outcome = round.immediate_trick_outcome();
+        assert_eq!(
+            outcome,
+            vec![
+                (Action::PlayCard(Card::new(12)), Some((1, 6))),
+                (Action::PlayCard(Card::new(20)), Some((3, 6)))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trump() {
+        let mut round = Round::new(2);
+        assert_eq!(round.trump(), None);
+
+        round.apply_action(Action::PickTrump(Some(Suit::Clubs)));
+        assert_eq!(round.trump(), Some(Suit::Clubs));
+
+        round.setup_for_next_round();
+        assert_eq!(round.trump(), None);
+
+        round.apply_action(Action::PickTrump(None));
+        assert_eq!(round.trump(), None);
+    }
+
+    #[test]
+    fn test_solo_teams() {
+        let play = |contract: Action| {
+            let mut round = Round::default();
+            round.set_dealer(0);
+            round.player_cards[1] = Stack::from_slice(&[2]);
+            round.player_cards[2] = Stack::from_slice(&[1, 7]);
+
+            round.apply_action(contract);
+            round.apply_action(Action::PlayCard(Card::new(2)));
+            round.possible_actions()
+        };
+
+        // player 2 has to buy when playing against player 1...
+        let actions = play(Action::PickTrump(None)).to_vec();
+        assert_eq!(actions, vec![Action::PlayCard(Card::new(7))]);
+
+        // ...but not when they're both playing against the declarer
+        let actions = play(Action::PickSolo).to_vec();
+        assert_eq!(
+            actions,
+            vec![
+                Action::PlayCard(Card::new(1)),
+                Action::PlayCard(Card::new(7))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_team_of() {
+        assert_eq!([0, 1, 2, 3].map(team_of), [0, 1, 0, 1]);
+        assert_eq!([0, 1, 2, 3].map(partner_of), [2, 3, 0, 1]);
+
+        for seat in 0..4 {
+            assert_eq!(team_of(seat), team_of(partner_of(seat)));
+            assert_ne!(team_of(seat), team_of((seat + 1) % 4));
+        }
+    }
+
+    #[test]
+    fn test_is_forced() {
+        let mut round = Round::new(0);
+        assert!(!round.is_forced());
+
+        while !round.is_terminal() {
+            if round.tricks_played() == 7 {
+                assert!(round.is_forced());
+            }
+
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+        }
+    }
+
+    #[test]
+    fn test_min_following_possible() {
+        // every player holds a single suit
+        let player_cards = std::array::from_fn(|i| Stack::ALL.of_suit(Suit::from(i as u8)));
+        let mut round = Round {
+            player_cards,
+            ..Default::default()
+        };
+        round.set_dealer(0);
+        round.apply_action(Action::PickTrump(None));
+
+        // the player to move holds every club
+        assert_eq!(round.min_following_possible(Suit::Clubs), 0);
+        assert_eq!(round.min_following_possible(Suit::Hearts), 2);
+
+        for card in [8, 16, 24, 0] {
+            round.apply_action(Action::PlayCard(Card::new(card)));
+        }
+
+        assert!(round.is_void(0, Suit::Clubs) && round.is_void(2, Suit::Clubs));
+        assert!(!round.is_void(2, Suit::Hearts));
+
+        // look from the perspective of player 3, both opponents are known to be void
+        round.turn = 3;
+        assert_eq!(round.min_following_possible(Suit::Clubs), 0);
+        assert_eq!(round.min_following_possible(Suit::Spades), 2);
+
+        round.turn = 2;
+        assert_eq!(round.min_following_possible(Suit::Clubs), 1);
+    }
+
+    #[test]
+    fn test_outcome_sign() {
+        let mut round = finished_round(Contract::Trump(Suit::Hearts), [2, 2, 2, 2]);
+
+        round.scores = [42, 18];
+        assert_eq!(round.outcome_sign(0), 1);
+        assert_eq!(round.outcome_sign(1), -1);
+
+        round.scores = [30, 30];
+        assert_eq!(round.outcome_sign(2), 0);
+        assert_eq!(round.outcome_sign(3), 0);
+
+        let round = finished_round(Contract::Misere { declarer: 2 }, [3, 2, 0, 3]);
+        assert_eq!(round.outcome_sign(2), 1);
+        assert_eq!(round.outcome_sign(0), -1);
+    }
+
+    #[test]
+    fn test_solve_double_dummy() {
+        let player_cards = [
+            Stack::from_slice(&[23, 31]),
+            Stack::from_slice(&[8, 9]),
+            Stack::from_slice(&[24, 25]),
+            Stack::from_slice(&[10, 11]),
+        ];
+        let mut round = Round {
+            player_cards,
+            played_cards: Stack::ALL
+                ^ player_cards
+                    .into_iter()
+                    .fold(Stack::default(), |a, b| a | b),
+            turn: 1,
+            tricks: [3, 1, 1, 1],
+            phase: RoundPhase::PlayCards,
+            ..Default::default()
+        };
+
+        // only player 1 and 3 hold clubs
+        assert_eq!(round.solve_double_dummy(), [0, 2]);
+
+        // player 2 has to ruff, after which player 0's ten of diamonds is the highest card left
+        round.contract = Contract::Trump(Suit::Diamonds);
+        round.trick.set_trump(Some(Suit::Diamonds));
+        assert_eq!(round.solve_double_dummy(), [2, 0]);
+
+        round.apply_action(Action::PlayCard(Card::new(8)));
+        assert_eq!(round.solve_double_dummy(), [2, 0]);
+    }
+
+    #[test]
+    fn test_points_remaining() {
+        let mut round = Round::new(0);
+        assert_eq!(round.points_remaining(), 60);
+
+        while !round.is_terminal() {
+            if round.cards_in_current_trick() == 0 {
+                assert_eq!(
+                    round.points_remaining() + round.scores().iter().sum::<i16>(),
+                    60
+                );
+            }
+
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+        }
+
+        assert_eq!(round.points_remaining(), 0);
+    }
+
+    #[test]
+    fn test_pass_trump() {
+        let mut round = Round::new(2);
+        assert!(!round.is_legal(&Action::PassTrump));
+
+        round.set_allow_pass(true);
+        for i in 0..3 {
+            assert_eq!(round.turn(), (2 + i) % 4);
+            assert!(round.is_legal(&Action::PassTrump));
+            round.apply_action(Action::PassTrump);
+        }
+
+        assert_eq!(round.turn(), 1);
+        assert!(!round.is_legal(&Action::PassTrump));
+
+        round.apply_action(Action::PickSolo);
+        assert_eq!(round.contract(), Contract::Solo { declarer: 1 });
+        assert_eq!(round.turn(), 3);
+    }
+
+    #[test]
+    fn test_eq() {
+        let play = |round: &mut Round, cards: &[u32]| {
+            for &card in cards {
+                round.apply_action(Action::PlayCard(Card::new(card)));
+            }
+        };
+
+        let player_cards = std::array::from_fn(|i| Stack::ALL.of_suit(Suit::from(i as u8)));
+        let mut a = Round {
+            player_cards,
+            ..Default::default()
+        };
+        a.set_dealer(0);
+        a.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+        let mut b = a;
+
+        play(&mut a, &[8, 16, 24, 0]);
+        play(&mut b, &[8, 16, 24, 0]);
+        assert_eq!(a, b);
+
+        b.apply_action(Action::PlayCard(Card::new(9)));
+        assert_ne!(a, b);
+
+        // only the card led differs
+        let mut c = a;
+        let mut d = a;
+        play(&mut c, &[9, 17]);
+        play(&mut d, &[10, 17]);
+        assert_ne!(c, d);
+    }
+
+    #[test]
+    fn test_predict_trick() {
+        let hands = [
+            Stack::from_slice(&[0, 8, 9]),
+            Stack::from_slice(&[6, 10, 11]),
+            Stack::from_slice(&[2, 7, 20]),
+            Stack::from_slice(&[4, 12, 21]),
+        ];
+        let undealt = !hands.iter().fold(Stack::default(), |a, &b| a | b);
+        let mut round = Round::from_parts(3, hands, undealt).unwrap();
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+
+        // seven of spades, ace of spades
+        for index in [0, 6] {
+            round.apply_action(Action::PlayCard(Card::new(index)));
+        }
+
+        // player 2 buys with the ten of spades, player 3 has to follow with the queen
+        assert_eq!(round.predict_trick(&GreedyRollout), (0, 11));
+        assert_eq!(round.cards_in_current_trick(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "no trick left to predict")]
+    fn test_predict_trick_terminal() {
+        let mut round = Round::new(1);
+        round.apply_action(Action::PickTrump(None));
+        while !round.is_terminal() {
+            round.apply_action(round.possible_actions().pop_random().unwrap());
+        }
+
+        round.predict_trick(&GreedyRollout);
+    }
+
+    #[test]
+    fn test_cards_to_move() {
+        let mut round = Round::new(2);
+        round.set_allow_pass(true);
+
+        while !round.is_terminal() {
+            assert!(round.cards_to_move() == round.player_cards(round.turn()));
+
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut round = Round::new(0);
+        round.set_allow_pass(true);
+        round.apply_action(Action::PassTrump);
+
+        for num_actions in [0, 1, 9, 20] {
+            let mut next = round;
+            for _ in 0..num_actions {
+                next.apply_action(next.possible_actions().pop_random().unwrap());
+            }
+
+            let snapshot = next.snapshot();
+            let original = next;
+
+            while !next.is_terminal() {
+                next.apply_action(next.possible_actions().pop_random().unwrap());
+            }
+            assert_ne!(next, original);
+
+            next.restore(&snapshot);
+            assert_eq!(next, original);
+        }
+    }
+
+    #[test]
+    fn test_guaranteed_winners() {
+        let mut round = Round::new(3);
+        round.player_cards[3] = Stack::from_slice(&[3, 7, 20, 21]);
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+
+        // seven of spades, ace of spades, eight of spades
+        for index in [0, 6, 1] {
+            round.apply_action(Action::PlayCard(Card::new(index)));
+        }
+
+        // the ten of spades is the only spade that beats the ace
+        assert!(round.guaranteed_winners() == Stack::from_slice(&[7]));
+
+        // without spades, every trump wins
+        round.player_cards[3] = Stack::from_slice(&[12, 20, 21]);
+        assert!(round.guaranteed_winners() == Stack::from_slice(&[20, 21]));
+
+        // having to follow with a lower spade never wins
+        round.player_cards[3] = Stack::from_slice(&[2, 20]);
+        assert!(round.guaranteed_winners().is_empty());
+    }
+
+    #[test]
+    fn test_guaranteed_winners_own_trumps() {
+        let hands = [
+            Stack::from_slice(&[0, 8, 9, 10]),
+            Stack::from_slice(&[7, 11, 22, 23]),
+            Stack::from_slice(&[1, 2, 12, 13]),
+            Stack::from_slice(&[3, 4, 14, 24]),
+        ];
+        let undealt = !hands.into_iter().fold(Stack::default(), |a, b| a | b);
+        let mut round = Round::from_parts(3, hands, undealt).unwrap();
+        round.apply_action(Action::PickTrump(Some(Suit::Hearts)));
+        round.apply_action(Action::PlayCard(Card::new(0)));
+
+        // player 1 has to follow with the ten of spades, and holds every trump left
+        assert!(round.guaranteed_winners() == Stack::from_slice(&[7]));
+    }
+
+    #[test]
+    fn test_short_round() {
+        let mut round = Round::new_with_hand_size(1, 4);
+        assert_eq!(round.num_tricks(), 4);
+        assert_eq!(round.unplayed_cards().len(), 16);
+        assert!(round.unplayed_cards() & round.undealt_cards() == Stack::default());
+        for i in 0..4 {
+            assert_eq!(round.player_cards(i).len(), 4);
+        }
+
+        let mut num_cards = 0;
+        round.apply_action(Action::PickTrump(None));
+        while !round.is_terminal() {
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+            num_cards += 1;
+        }
+
+        assert_eq!(num_cards, 16);
+        assert_eq!(round.tricks_played(), 4);
+        assert_eq!(round.tricks().iter().sum::<u8>(), 4);
+        assert_eq!(round.points_remaining(), 0);
+        assert_eq!(round.scores().iter().sum::<i16>(), round.points_in_play());
+
+        round.setup_for_next_round();
+        assert_eq!(round.num_tricks(), 4);
+        assert!(!round.is_terminal());
+    }
+
+    #[test]
+    fn test_sample_worlds() {
+        let mut round = Round::new(0);
+        while round.tricks_played() < 4 {
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+        }
+
+        for world in round.sample_worlds(1, 200) {
+            assert!(world.player_cards(1) == round.player_cards(1));
+            assert!(world.played_cards() == round.played_cards());
+
+            let mut seen = world.played_cards();
+            for i in 0..4 {
+                let cards = world.player_cards(i);
+                assert!((seen & cards).is_empty());
+                assert_eq!(cards.len(), round.player_cards(i).len());
+                seen |= cards;
+
+                for suit in (0..4).map(Suit::from) {
+                    assert!(!round.is_void(i, suit) || !cards.has_suit(suit));
+                }
+            }
+            assert!(seen == Stack::ALL);
+        }
+    }
+
+    #[test]
+    fn test_sample_worlds_tight_voids() {
+        // every suit left is only wanted by two players, with room for exactly two cards each
+        let hands = [
+            Stack::from_slice(&[8, 9]),
+            Stack::from_slice(&[16, 24]),
+            Stack::from_slice(&[0, 25]),
+            Stack::from_slice(&[1, 17]),
+        ];
+        let undealt = !hands.iter().fold(Stack::default(), |a, &b| a | b);
+        let mut round = Round::from_parts(0, hands, undealt).unwrap();
+        round.voids = [
+            0,
+            1 << Suit::Spades as u8,
+            1 << Suit::Hearts as u8,
+            1 << Suit::Diamonds as u8,
+        ];
+
+        for world in round.sample_worlds(0, 200) {
+            for i in 1..4 {
+                let cards = world.player_cards(i);
+                assert_eq!(cards.len(), 2);
+                assert!((0..4)
+                    .map(Suit::from)
+                    .all(|suit| !round.is_void(i, suit) || !cards.has_suit(suit)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_trick_order() {
+        let mut round = Round::new(1);
+        round.apply_action(Action::PickTrump(None));
+        assert_eq!(round.trick_order(), [2, 3, 0, 1]);
+
+        for _ in 0..3 {
+            let action = round.possible_actions().pop_random().unwrap();
+            round.apply_action(action);
+            assert_eq!(round.trick_order(), [2, 3, 0, 1]);
+        }
+
+        let action = round.possible_actions().pop_random().unwrap();
+        round.apply_action(action);
+
+        let leader = round.turn();
+        assert_eq!(round.trick_order()[0], leader);
+        assert_eq!(round.trick_order()[3], (leader + 3) % 4);
     }
 }
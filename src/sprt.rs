@@ -20,7 +20,9 @@ pub fn run_sprt(num_threads: usize, player_gen: impl Fn() -> PlayerVec + Clone +
                     game.play_round();
                 }
 
-                num_wins.lock().unwrap()[game.winner()] += 1.;
+                if let Some(team) = game.winner() {
+                    num_wins.lock().unwrap()[team] += 1.;
+                }
                 *num_games.lock().unwrap() += 1.;
 
                 let w = *num_wins.lock().unwrap();
@@ -15,14 +15,14 @@ pub const DIAMONDS: u32 = SPADES << 24;
 
 pub const ALL: u32 = SPADES | CLUBS | HEARTS | DIAMONDS;
 
-const TENS: u32 = 1 << 7 | 1 << 15 | 1 << 23 | 1 << 31;
-const ACES: u32 = TENS >> 1;
-const KINGS: u32 = TENS >> 2;
-const QUEENS: u32 = TENS >> 3;
-const JACKS: u32 = TENS >> 4;
-const NINES: u32 = TENS >> 5;
-const EIGHTS: u32 = TENS >> 6;
-const SEVENS: u32 = TENS >> 7;
+pub const TENS: u32 = 1 << 7 | 1 << 15 | 1 << 23 | 1 << 31;
+pub const ACES: u32 = TENS >> 1;
+pub const KINGS: u32 = TENS >> 2;
+pub const QUEENS: u32 = TENS >> 3;
+pub const JACKS: u32 = TENS >> 4;
+pub const NINES: u32 = TENS >> 5;
+pub const EIGHTS: u32 = TENS >> 6;
+pub const SEVENS: u32 = TENS >> 7;
 
 const HIGHEST_CARD: u32 = ACES;
 const LOWEST_CARD: u32 = SEVENS;
@@ -66,6 +66,10 @@ impl Stack {
         cards
     }
 
+    pub const fn get_data(&self) -> u32 {
+        self.data
+    }
+
     pub const fn set_data(&mut self, data: u32) {
         self.data = data;
     }
@@ -188,6 +192,17 @@ impl Stack {
         }
     }
 
+    /// the cards of each suit, in the order of [`Suit::ALL`]
+    pub const fn by_suit(&self) -> [Stack; 4] {
+        let [a, b, c, d] = Suit::ALL;
+        [
+            self.of_suit(a),
+            self.of_suit(b),
+            self.of_suit(c),
+            self.of_suit(d),
+        ]
+    }
+
     pub fn above(&self, card: Card) -> Stack {
         *self & Self::all_above(card)
     }
@@ -208,9 +223,14 @@ impl Stack {
         self.data & 1 << index != 0
     }
 
+    /// number of cards in this stack, at most 32
     pub const fn len(&self) -> u32 {
         self.data.count_ones()
     }
+
+    pub const fn is_empty(&self) -> bool {
+        self.data == 0
+    }
 }
 
 impl PartialEq for Stack {
@@ -219,6 +239,8 @@ impl PartialEq for Stack {
     }
 }
 
+impl Eq for Stack {}
+
 impl PartialEq<u32> for Stack {
     fn eq(&self, other: &u32) -> bool {
         self.data == *other
@@ -311,6 +333,12 @@ impl BitXor<u32> for Stack {
     }
 }
 
+impl BitXorAssign for Stack {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.data ^= rhs.data;
+    }
+}
+
 impl BitXorAssign<u32> for Stack {
     fn bitxor_assign(&mut self, rhs: u32) {
         self.data ^= rhs;
@@ -333,6 +361,17 @@ impl BitXor<Stack> for u32 {
     }
 }
 
+impl FromIterator<Card> for Stack {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut stack = Stack::default();
+        for card in iter {
+            stack.push(card);
+        }
+
+        stack
+    }
+}
+
 pub struct CardIterator(u32);
 
 impl Iterator for CardIterator {
@@ -360,3 +399,72 @@ impl fmt::Debug for Stack {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Stack;
+    use crate::suit::Suit;
+
+    #[test]
+    fn test_len() {
+        assert_eq!(Stack::ALL.len(), 32);
+        assert_eq!(Stack::default().len(), 0);
+        assert_eq!(Stack::from_slice(&[0, 9, 31]).len(), 3);
+        assert_eq!((Stack::ALL & super::SPADES).len(), 8);
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = Stack::from_slice(&[0, 1, 2, 31]);
+        let b = Stack::from_slice(&[2, 3, 31]);
+
+        assert!(a | b == Stack::from_slice(&[0, 1, 2, 3, 31]));
+        assert!(a & b == Stack::from_slice(&[2, 31]));
+        assert!(a ^ b == Stack::from_slice(&[0, 1, 3]));
+
+        let mut c = a;
+        c |= b;
+        assert!(c == a | b);
+
+        let mut c = a;
+        c &= b;
+        assert!(c == a & b);
+
+        let mut c = a;
+        c ^= b;
+        assert!(c == a ^ b);
+    }
+
+    #[test]
+    fn test_not() {
+        assert!(!Stack::default() == Stack::ALL);
+        assert!(!Stack::ALL == Stack::default());
+
+        let a = Stack::from_slice(&[0, 31]);
+        assert_eq!((!a).len(), 30);
+        assert!(!!a == a);
+    }
+
+    #[test]
+    fn test_by_suit() {
+        let stack = Stack::from_slice(&[0, 5, 9, 17, 18, 23, 31]);
+        let suits = stack.by_suit();
+
+        let mut union = Stack::default();
+        for (i, suit) in suits.into_iter().enumerate() {
+            assert!((union & suit).is_empty());
+            assert!(suit == stack.of_suit(Suit::ALL[i]));
+            union |= suit;
+        }
+
+        assert!(union == stack);
+        assert_eq!(suits.map(|suit| suit.len()), [2, 1, 3, 1]);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(Stack::default().is_empty());
+        assert!(!Stack::ALL.is_empty());
+        assert!(!Stack::from_slice(&[31]).is_empty());
+    }
+}
@@ -12,6 +12,9 @@ pub enum Suit {
 }
 
 impl Suit {
+    /// every suit, in the order of their index
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Clubs, Suit::Hearts, Suit::Diamonds];
+
     pub const fn mask(&self) -> u32 {
         match self {
             Suit::Spades => SPADES,
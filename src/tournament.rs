@@ -3,7 +3,105 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{game::Game, players::PlayerVec};
+use crate::{
+    bench::MatchStats,
+    game::Game,
+    players::{
+        heuristic_player::HeuristicPlayer, mcts_player::MctsPlayer, random_player::RandomPlayer,
+        Player, PlayerVec,
+    },
+    rng,
+    rollout::GreedyRollout,
+    round::team_of,
+};
+
+/// one of the bots taking part in a [`tournament`], both players of a team use the same one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotConfig {
+    Random {
+        partner_aware: bool,
+    },
+    Heuristic {
+        samples: usize,
+    },
+    Mcts {
+        search_time: u128,
+        use_inference: bool,
+        /// play rollouts with [`GreedyRollout`] instead of uniformly random actions
+        greedy_rollout: bool,
+    },
+}
+
+impl BotConfig {
+    pub fn build(&self) -> Box<dyn Player> {
+        match *self {
+            BotConfig::Random { partner_aware } => Box::new(RandomPlayer::new(partner_aware)),
+            BotConfig::Heuristic { samples } => Box::new(HeuristicPlayer::new(samples)),
+            BotConfig::Mcts {
+                search_time,
+                use_inference,
+                greedy_rollout,
+            } => {
+                let player = MctsPlayer::new(search_time, use_inference);
+                match greedy_rollout {
+                    true => Box::new(player.set_rollout_policy(&GreedyRollout)),
+                    false => Box::new(player),
+                }
+            }
+        }
+    }
+}
+
+/// play every pair of `configs` against each other on `games_per_pair` seeds.
+/// Every seed is played twice, once with either config as team 0, so both get the same
+/// cards. Entry `[i][j]` holds the results from the perspective of `configs[i]`,
+/// the diagonal is empty. Bots that only use the thread-local generator make the
+/// whole tournament reproducible from `base_seed`
+pub fn tournament(
+    configs: &[BotConfig],
+    games_per_pair: usize,
+    base_seed: u64,
+) -> Vec<Vec<MatchStats>> {
+    let n = configs.len();
+    let mut results = vec![vec![MatchStats::default(); n]; n];
+
+    for i in 0..n {
+        for j in i + 1..n {
+            let pair_seed = rng::split(base_seed, (i * n + j) as u64);
+
+            for game in 0..games_per_pair {
+                let seed = rng::split(pair_seed, game as u64);
+
+                let scores = play_seeded(configs[i], configs[j], seed);
+                results[i][j].record(scores);
+
+                let [a, b] = play_seeded(configs[j], configs[i], seed);
+                results[i][j].record([b, a]);
+            }
+
+            results[j][i] = results[i][j].mirrored();
+        }
+    }
+
+    results
+}
+
+/// the final scores of a game between `team_0` and `team_1`, seeded with `seed`
+fn play_seeded(team_0: BotConfig, team_1: BotConfig, seed: u64) -> [i16; 2] {
+    let players: PlayerVec = (0..4)
+        .map(|seat| match team_of(seat) {
+            0 => team_0.build(),
+            _ => team_1.build(),
+        })
+        .collect();
+
+    let mut game = Game::new_seeded(players, seed);
+    while !game.is_terminal() {
+        game.play_round();
+    }
+
+    game.scores()
+}
 
 #[derive(Default, Debug)]
 struct TournamentResult {
@@ -68,11 +166,17 @@ fn run_tournament(
         }
 
         total_rounds += game.num_rounds();
+
+        if verbose {
+            println!("{:?}", game.winner());
+        }
     }
 
     let mut score = [0; 2];
     for game in &mut games {
-        score[game.winner()] += 1;
+        if let Some(team) = game.winner() {
+            score[team] += 1;
+        }
     }
 
     TournamentResult {
@@ -82,3 +186,28 @@ fn run_tournament(
         duration: start.elapsed(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{tournament, BotConfig};
+
+    #[test]
+    fn test_paired_tournament() {
+        const GAMES: usize = 4;
+
+        let random = BotConfig::Random {
+            partner_aware: false,
+        };
+        let results = tournament(&[random, random], GAMES, 7);
+
+        assert_eq!(results[0][0].num_games(), 0);
+        assert_eq!(results[0][1].num_games(), 2 * GAMES);
+        assert_eq!(results[0][1], results[1][0]);
+
+        // identical bots on the same cards win exactly half of the games
+        assert_eq!(results[0][1].wins[0], results[0][1].wins[1]);
+        assert_eq!(results[0][1].avg_margin(0), 0.);
+
+        assert_eq!(tournament(&[random, random], GAMES, 7), results);
+    }
+}
@@ -1,11 +1,13 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 use crate::{array::Array, card::Card, suit::Suit};
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct Trick {
     /// TODO: might be able to remove this field
     cards: Array<Card, 4>,
+    /// the player that played the first card
+    leader: usize,
     trump: Option<Suit>,
     winner: Option<(Card, usize)>,
     score: i16,
@@ -15,6 +17,7 @@ impl Trick {
     /// clear all properties except trump
     pub const fn clear(&mut self) {
         self.cards.clear();
+        self.leader = 0;
         self.winner = None;
         self.score = 0;
     }
@@ -26,26 +29,16 @@ impl Trick {
     pub fn play(&mut self, card: Card, player: usize) {
         match self.winner {
             // this is the first card of this trick
-            None => self.winner = Some((card, player)),
-            Some((winner_card, _)) => match self.trump {
-                // playing with trump
-                Some(trump) => {
-                    let winner_suit = winner_card.suit();
-                    let card_suit = card.suit();
-
-                    if (card_suit == trump && winner_suit != trump)
-                        || (card.value() > winner_card.value() && card_suit == winner_suit)
-                    {
-                        self.winner = Some((card, player));
-                    }
+            None => {
+                self.winner = Some((card, player));
+                self.leader = player;
+            }
+            Some((winner_card, _)) => {
+                let led = self.cards[0].suit();
+                if card.beats(&winner_card, led, self.trump) {
+                    self.winner = Some((card, player));
                 }
-                // playing without trump
-                None => {
-                    if card.suit() == winner_card.suit() && card.value() > winner_card.value() {
-                        self.winner = Some((card, player));
-                    }
-                }
-            },
+            }
         }
 
         self.score += card.score();
@@ -56,6 +49,7 @@ impl Trick {
         self.winner
     }
 
+    /// the points of the cards played so far, which also works for an unfinished trick
     pub const fn score(&self) -> i16 {
         self.score
     }
@@ -68,8 +62,35 @@ impl Trick {
         self.trump
     }
 
+    /// the player that played the first card, if any
+    pub const fn leader(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.leader)
+        }
+    }
+
+    /// the card that was played last and the player that played it
+    pub fn last_played(&self) -> Option<(Card, usize)> {
+        let len = self.len();
+        let card = self.cards.get(len.checked_sub(1)?)?;
+
+        Some((card, (self.leader + len - 1) % 4))
+    }
+
+    /// number of cards played in this trick
+    pub const fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// no cards have been played yet, i.e. the next player leads
+    pub const fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
     pub const fn is_finished(&self) -> bool {
-        self.cards.len() == 4
+        self.len() == 4
     }
 
     pub const fn cards(&self) -> Array<Card, 4> {
@@ -77,6 +98,14 @@ impl Trick {
     }
 }
 
+/// the cards in the order they were played
+impl Display for Trick {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards = self.cards.iter().map(|card| card.to_string());
+        write!(f, "{}", cards.collect::<Vec<_>>().join(" "))
+    }
+}
+
 impl Debug for Trick {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Trick")
@@ -86,3 +115,95 @@ impl Debug for Trick {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Trick;
+    use crate::{card::Card, suit::Suit};
+
+    fn play(trump: Option<Suit>, leader: usize, cards: [u32; 4]) -> Option<(Card, usize)> {
+        let mut trick = Trick::default();
+        trick.set_trump(trump);
+
+        for (i, index) in cards.into_iter().enumerate() {
+            trick.play(Card::new(index), (leader + i) % 4);
+        }
+
+        assert!(trick.is_finished());
+        trick.winner()
+    }
+
+    #[test]
+    fn test_highest_of_led_suit_wins() {
+        // nine of spades, ace of clubs, ten of spades, eight of spades
+        let winner = play(None, 1, [2, 14, 7, 1]);
+        assert_eq!(winner, Some((Card::new(7), 3)));
+    }
+
+    #[test]
+    fn test_trump_beats_led_suit() {
+        // ten of spades, seven of hearts, ace of spades, ace of clubs
+        let winner = play(Some(Suit::Hearts), 0, [7, 16, 6, 14]);
+        assert_eq!(winner, Some((Card::new(16), 1)));
+    }
+
+    #[test]
+    fn test_highest_trump_wins() {
+        // king of spades, eight of hearts, jack of hearts, seven of hearts
+        let winner = play(Some(Suit::Hearts), 2, [5, 17, 19, 16]);
+        assert_eq!(winner, Some((Card::new(19), 0)));
+    }
+
+    #[test]
+    fn test_first_card_wins_tie() {
+        // nobody follows or trumps, so cards of equal value don't beat the led seven
+        let winner = play(Some(Suit::Diamonds), 3, [0, 8, 16, 9]);
+        assert_eq!(winner, Some((Card::new(0), 3)));
+    }
+
+    #[test]
+    fn test_len() {
+        let mut trick = Trick::default();
+        assert!(trick.is_empty());
+        assert_eq!(trick.suit_to_follow(), None);
+
+        for (i, index) in [3, 11, 4, 5].into_iter().enumerate() {
+            trick.play(Card::new(index), i);
+            assert!(!trick.is_empty());
+            assert_eq!(trick.len(), i + 1);
+            assert_eq!(trick.is_finished(), i == 3);
+        }
+
+        trick.clear();
+        assert!(trick.is_empty());
+    }
+
+    #[test]
+    fn test_last_played() {
+        let mut trick = Trick::default();
+        assert_eq!(trick.last_played(), None);
+
+        for (i, index) in [3, 11, 4, 5].into_iter().enumerate() {
+            let seat = (2 + i) % 4;
+            trick.play(Card::new(index), seat);
+            assert_eq!(trick.last_played(), Some((Card::new(index), seat)));
+        }
+    }
+
+    #[test]
+    fn test_partial_score() {
+        let mut trick = Trick::default();
+        assert_eq!(trick.score(), 0);
+
+        // jack, queen, seven and ten of clubs
+        let mut points = 0;
+        for (i, index) in [11, 12, 8, 15].into_iter().enumerate() {
+            trick.play(Card::new(index), i);
+            points += Card::new(index).score();
+            assert_eq!(trick.score(), points);
+        }
+
+        assert!(trick.is_finished());
+        assert_eq!(trick.score(), 1 + 2 + 5);
+    }
+}
@@ -84,10 +84,10 @@ impl App {
         }
 
         let scores = self.round.scores();
-        let winning_team = if scores[0] > scores[1] { 0 } else { 1 };
-        self.scores[winning_team] += scores[winning_team] - 30;
+        let (winning_team, points) = self.round.result();
+        self.scores[winning_team] += points;
 
-        assert!(scores.iter().sum::<i16>() == 60);
+        assert!(scores.iter().sum::<i16>() == self.round.points_in_play());
         self.num_rounds += 1;
         self.round.setup_for_next_round();
         self.card_history.clear();
@@ -141,7 +141,7 @@ impl App {
             ui.vertical_centered(|ui| {
                 ui.horizontal(|ui| {
                     ui.label(format!("scores: {:?}", self.scores));
-                    ui.label(format!("trump: {:?}", self.round.trump()));
+                    ui.label(format!("contract: {}", self.round.contract()));
                 });
                 ui.label(format!("round score: {:?}", self.round.scores()));
             });
@@ -158,7 +158,7 @@ impl App {
                 ui.vertical_centered(|ui| {
                     self.show_cards(card_width * 1.6, &cards, ui);
                     if self.card_history.len() >= 4 {
-                        let in_this_round = self.round.trick_ref().cards().len();
+                        let in_this_round = self.round.cards_in_current_trick();
                         let end = self.card_history.len() - in_this_round;
                         let cards = self.card_history[(end - 4)..end].to_vec();
 
@@ -1,10 +1,11 @@
-use std::sync::LazyLock;
 use eframe::egui::{self, include_image, Image};
+use std::sync::LazyLock;
 
 pub mod app;
 
-static CARD_IMAGES: LazyLock<Vec<Image<'static>>> = LazyLock::new(|| store_images());
+static CARD_IMAGES: LazyLock<Vec<Image<'static>>> = LazyLock::new(store_images);
 
+#[allow(clippy::vec_init_then_push)]
 fn store_images() -> Vec<Image<'static>> {
     let mut images = vec![];
 
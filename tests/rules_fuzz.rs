@@ -0,0 +1,72 @@
+use ismcts::state::State;
+use manille_master::{
+    action::Action,
+    inference::Inference,
+    players::{random_player::RandomPlayer, Player},
+    rng,
+    round::Round,
+    stack::Stack,
+};
+
+const NUM_GAMES: u64 = 1000;
+
+fn check_invariants(round: &Round, num_played: u32, prev_scores: [i16; 2]) {
+    let mut seen = round.played_cards();
+    for i in 0..4 {
+        let cards = round.player_cards(i);
+        assert!((seen & cards).is_empty(), "hands overlap: {round:?}");
+        seen |= cards;
+    }
+
+    assert!(seen == Stack::ALL, "cards went missing: {round:?}");
+    assert_eq!(round.played_cards().len(), num_played);
+
+    let scores = round.scores();
+    assert!(scores[0] >= prev_scores[0] && scores[1] >= prev_scores[1]);
+    assert_eq!(
+        scores[0] + scores[1] + round.trick_ref().score() + round.points_remaining(),
+        60
+    );
+
+    assert_eq!(round.is_terminal(), num_played == 32);
+}
+
+#[test]
+fn fuzz_random_games() {
+    for game in 0..NUM_GAMES {
+        rng::seed_from(rng::split(0, game));
+
+        let mut players = [
+            RandomPlayer::new(false),
+            RandomPlayer::new(true),
+            RandomPlayer::with_seed(game),
+            RandomPlayer::new(true),
+        ];
+        let inference = Inference::default();
+        let mut round = Round::new(rng::range_usize(0..4));
+        let mut game_scores = [0; 2];
+
+        while game_scores.iter().all(|&s| s < 61) {
+            let mut num_played = 0;
+            check_invariants(&round, num_played, [0; 2]);
+
+            while !round.is_terminal() {
+                let action = players[round.turn()].decide(round, &inference);
+                assert!(round.is_legal(&action), "illegal {action} in game {game}");
+
+                let prev_scores = round.scores();
+                round.apply_action(action);
+                if let Action::PlayCard(_) = action {
+                    num_played += 1;
+                }
+
+                check_invariants(&round, num_played, prev_scores);
+            }
+
+            let (team, points) = round.result();
+            game_scores[team] += points;
+
+            round.setup_for_next_round();
+        }
+    }
+}
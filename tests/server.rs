@@ -0,0 +1,64 @@
+#![cfg(feature = "server")]
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use manille_master::{io::server::serve, rng};
+
+/// pick a random entry of the `legal` list of a state
+fn random_legal(state: &str) -> String {
+    let start = state.find("\"legal\":[").unwrap() + "\"legal\":[".len();
+    let legal = &state[start..state.rfind(']').unwrap()];
+
+    // split on the commas between entries, not the ones inside them
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut begin = 0;
+    for (i, c) in legal.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(&legal[begin..i]);
+                begin = i + 1;
+            }
+            _ => (),
+        }
+    }
+    entries.push(&legal[begin..]);
+
+    entries[rng::range_usize(0..entries.len())].to_owned()
+}
+
+#[test]
+fn play_over_loopback() {
+    rng::seed_from(7);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = thread::spawn(move || serve(&listener, 1, 5));
+
+    let stream = TcpStream::connect(address).unwrap();
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+
+    let mut num_moves = 0;
+    let winner = loop {
+        let mut line = String::new();
+        assert_ne!(reader.read_line(&mut line).unwrap(), 0, "server hung up");
+
+        if let Some(winner) = line.trim().strip_prefix("{\"winner\":") {
+            break winner.trim_end_matches('}').parse::<usize>().ok();
+        }
+
+        assert!(line.starts_with("{\"seat\":1,\"turn\":1,"), "{line}");
+        writeln!(writer, "{}", random_legal(&line)).unwrap();
+        num_moves += 1;
+    };
+
+    assert!(num_moves >= 8);
+    assert_eq!(server.join().unwrap().unwrap(), winner);
+}